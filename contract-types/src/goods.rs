@@ -0,0 +1,175 @@
+use marketplace_asset::auth::Signers;
+use marketplace_asset::metadata::{MediaInfo, MediaRef, MediaType};
+use marketplace_asset::upgrade::PendingUpgrade;
+use marketplace_asset::Error;
+use soroban_sdk::token::{StellarAssetInterface, TokenInterface};
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, BytesN, Env, String, Vec};
+
+/// Price of one good in the 'asset' with 'decimals' digits after the point
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Price {
+    pub amount: i128,
+    pub asset: Address,
+    pub decimals: u32,
+}
+
+/// Bulk price of one good in the asset of the base price,
+/// applied when at least 'min_quantity' goods are bought
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PriceTier {
+    pub min_quantity: i128,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PriceChange {
+    pub price: Price,
+    /// tiers sorted by 'min_quantity'
+    pub tiers: Vec<PriceTier>,
+    /// ledger timestamp the price is applied from
+    pub effective_from: u64,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Reservation {
+    pub quantity: i128,
+    /// ledger timestamp the reservation can be released by anyone after
+    pub expires_at: u64,
+}
+
+/// Metadata is the goods description for portals, the current price is returned by 'price'
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Metadata {
+    pub id: String,
+    pub admin: Address,
+    pub name: String,
+    pub description: String,
+    pub amount: u64,
+    pub toml: String,
+    pub media_list: Vec<String>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum StorageKey {
+    /// Balance is an amount of goods tokens held by the address. Value is i128.
+    Balance(Address),
+    /// Allowance is an amount the spender can transfer from the address. Value is AllowanceValue.
+    Allowance(Address, Address),
+    /// Deauthorized marks the address that can't use its goods tokens. Value is bool.
+    Deauthorized(Address),
+    /// Reservation is an amount of goods held for the buyer. Value is Reservation.
+    Reservation(Address),
+}
+
+/// Goods smart contract, a token of the goods with the stock, prices and media
+#[contractclient(name = "GoodsClient")]
+pub trait GoodsInterface: StellarAssetInterface + TokenInterface {
+    fn set_name(env: Env, name: String);
+    fn set_description(env: Env, description: String);
+    fn set_price(env: Env, price: Price, tiers: Vec<PriceTier>);
+    #[doc=" Schedule the price change at the effective ledger timestamp"]
+    fn schedule_price(env: Env, price: Price, tiers: Vec<PriceTier>, effective_from: u64);
+    #[doc=" Cancel the price change scheduled at the effective ledger timestamp"]
+    fn cancel_price(env: Env, effective_from: u64);
+    #[doc=" return the current price"]
+    fn price(env: Env) -> PriceChange;
+    #[doc=" return all price changes including scheduled ones"]
+    fn price_history(env: Env) -> Vec<PriceChange>;
+    #[doc=" return total price of the quantity of goods at the ledger timestamp"]
+    fn quote(env: Env, quantity: i128, timestamp: Option<u64>) -> Price;
+    fn set_amount(env: Env, amount: u64);
+    #[doc=" Add goods to the stock"]
+    fn restock(env: Env, quantity: u64);
+    #[doc=" Hold goods for the buyer until the expiration ledger timestamp"]
+    fn reserve(env: Env, buyer: Address, quantity: i128, expires_at: u64);
+    #[doc=" Return reserved goods of the buyer to the available stock"]
+    fn release_reservation(env: Env, buyer: Address);
+    #[doc=" Deliver goods to the holder and burn its goods tokens"]
+    fn fulfil(env: Env, holder: Address, quantity: i128);
+    #[doc=" return reservation of the buyer"]
+    fn reservation(env: Env, buyer: Address) -> Option<Reservation>;
+    #[doc=" return amount of goods that can be reserved or minted"]
+    fn available(env: Env) -> i128;
+    #[doc=" return amount of goods held by reservations"]
+    fn reserved(env: Env) -> i128;
+    #[doc=" return amount of goods delivered to holders"]
+    fn sold(env: Env) -> i128;
+    fn media_max_block_size() -> u64;
+    fn add_media(
+        env: Env,
+        media_id: String,
+        media_type: MediaType,
+        media: String,
+        total_blocks: u64,
+        media_hash: BytesN<32>,
+        media_ref: Option<MediaRef>,
+    );
+    fn upload_media_block(
+        env: Env,
+        media_id: String,
+        media: Bytes,
+        block_number: u64,
+        block_hash: BytesN<32>,
+    );
+    fn remove_media_block(env: Env, media_id: String, block_number: u64);
+    fn remove_media(env: Env, media_id: String);
+    #[doc=" return true if all media blocks are uploaded and match the media hash"]
+    fn is_media_complete(env: Env, media_id: String) -> bool;
+    fn set_toml_file(env: Env, toml_file_link: String);
+    #[doc=" return the goods description with the media list"]
+    fn metadata(env: Env) -> Metadata;
+    #[doc=" return unique id of the goods"]
+    fn id(env: Env) -> String;
+    #[doc=" return description of the goods"]
+    fn description(env: Env) -> String;
+    #[doc=" return stock of the goods"]
+    fn amount(env: Env) -> u64;
+    #[doc=" return link to the toml file"]
+    fn toml_file(env: Env) -> String;
+    #[doc=" return ids of all media"]
+    fn media_list(env: Env) -> Vec<String>;
+    #[doc=" return media description"]
+    fn media(env: Env, media_id: String) -> MediaInfo;
+    #[doc=" return media block"]
+    fn media_block(env: Env, media_id: String, block_number: u64) -> String;
+    #[doc=" return media block as bytes, read blocks 1..=total_blocks to stream the media"]
+    fn media_chunk(env: Env, media_id: String, block_number: u64) -> Bytes;
+    #[doc=" return MIME type of the media"]
+    fn media_mime_type(env: Env, media_id: String) -> String;
+    #[doc=" return reference to the off-chain media content"]
+    fn media_ref(env: Env, media_id: String) -> MediaRef;
+    #[doc=" Replace the keys that sign for the goods contract as an account,"]
+    #[doc=" at least 'threshold' of them must sign, no keys remove the signers"]
+    fn set_signers(env: Env, keys: Vec<BytesN<32>>, threshold: u32);
+    #[doc=" return the keys that sign for the goods contract"]
+    fn signers(env: Env) -> Option<Signers>;
+    #[doc=" Extend time to live of the goods description, media and the given storage entries"]
+    fn extend_all(env: Env, keys: Vec<StorageKey>);
+    #[doc=" return version description"]
+    fn version_build(env: Env) -> String;
+    #[doc=" return version of the code"]
+    fn version() -> i32;
+    #[doc=" return storage schema version of the smart contract"]
+    fn schema_version(env: Env) -> u32;
+    #[doc=" Migrate the storage to the schema version of the code after upgrade"]
+    fn migrate(env: Env) -> Result<u32, Error>;
+    #[doc=" return minimal delay between scheduling and execution of the upgrade in seconds"]
+    fn upgrade_delay(env: Env) -> u64;
+    #[doc=" Set minimal delay between scheduling and execution of the upgrade in seconds,"]
+    #[doc=" a shorter delay applies after the current one"]
+    fn set_upgrade_delay(env: Env, delay: u64);
+    #[doc=" return the upgrade scheduled by the admin"]
+    fn pending_upgrade(env: Env) -> Option<PendingUpgrade>;
+    #[doc=" Schedule upgrade of smart contract to the hash of installed code"]
+    fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64);
+    #[doc=" Cancel the scheduled upgrade"]
+    fn cancel_upgrade(env: Env);
+    #[doc=" Upgrade smart contract after the scheduled time"]
+    fn execute_upgrade(env: Env);
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+#![no_std]
+
+//! Interfaces and types of the smart contracts, so their clients can be imported
+//! by the other smart contracts of the marketplace
+
+pub mod goods;
+pub mod mint;
+pub mod oracle;
@@ -0,0 +1,10 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Asset contract the master contract mints, claws back and authorizes holders of
+#[contractclient(name = "MintClient")]
+pub trait MintInterface {
+    fn mint(env: Env, to: Address, amount: i128);
+    fn set_admin(env: Env, new_admin: Address);
+    fn clawback(env: Env, from: Address, amount: i128);
+    fn set_authorized(env: Env, id: &Address, authorize: &bool);
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Env, String};
+
+/// Price feed the master contract reads exchange rates from
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeedInterface {
+    /// Return the price of one 'base' unit in 'quote' scaled by 10^7 and the timestamp of the price
+    fn rate(env: Env, base: String, quote: String) -> (i128, u64);
+}
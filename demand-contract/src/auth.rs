@@ -1,19 +1,14 @@
-use crate::contract::DemandContract;
-use crate::error::Error;
+use crate::contract::{DemandContract, DemandContractArgs, DemandContractClient};
+use crate::escrow::BudgetEscrow;
+use marketplace_asset::auth::{authenticate, AccSignature};
+use marketplace_asset::Error;
 use soroban_sdk::{
     auth::{Context, CustomAccountInterface},
-    contracttype,
     crypto::Hash,
-    BytesN, Env, Vec,
+    contractimpl, Env, Vec,
 };
 
-#[contracttype]
-#[derive(Clone)]
-pub struct AccSignature {
-    pub public_key: BytesN<32>,
-    pub signature: BytesN<64>,
-}
-
+#[contractimpl]
 impl CustomAccountInterface for DemandContract {
     type Signature = Vec<AccSignature>;
     type Error = Error;
@@ -47,32 +42,22 @@ impl CustomAccountInterface for DemandContract {
         env: Env,
         signature_payload: Hash<32>,
         signatures: Vec<AccSignature>,
-        _: Vec<Context>,
+        auth_context: Vec<Context>,
     ) -> Result<(), Error> {
         // Perform authentication.
         authenticate(&env, &signature_payload, &signatures)?;
-        Ok(())
-    }
-}
 
-fn authenticate(
-    env: &Env,
-    signature_payload: &Hash<32>,
-    signatures: &Vec<AccSignature>,
-) -> Result<(), Error> {
-    for i in 0..signatures.len() {
-        let signature = signatures.get_unchecked(i);
-        if i > 0 {
-            let prev_signature = signatures.get_unchecked(i - 1);
-            if prev_signature.public_key >= signature.public_key {
-                return Err(Error::BadSignatureOrder);
+        // The escrowed budget moves only through the escrow entry points,
+        // signers of the smart contract can't spend the pay asset.
+        if let Some(escrow) = BudgetEscrow::escrow(&env) {
+            for context in auth_context.iter() {
+                if let Context::Contract(c) = context {
+                    if c.contract == escrow.asset {
+                        return Err(Error::InvalidContext);
+                    }
+                }
             }
         }
-        env.crypto().ed25519_verify(
-            &signature.public_key,
-            &signature_payload.clone().into(),
-            &signature.signature,
-        );
+        Ok(())
     }
-    Ok(())
 }
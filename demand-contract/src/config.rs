@@ -0,0 +1,65 @@
+use soroban_sdk::{contracttype, Address, Env, String};
+use marketplace_asset::admin::AdminConfig;
+use marketplace_asset::Error;
+use crate::schema::Schema;
+use crate::store::{ADMIN, DESCRIPTION, ID, NAME, BUDGET, TOML, CONFIG};
+
+/// Config is the demand description kept in the instance storage
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    /// unique id of the demand
+    pub(crate) id: String,
+    /// admin address to manage the smart contract
+    pub(crate) admin: Address,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    /// budget of a demand
+    pub(crate) budget: u64,
+    /// link to the toml file
+    pub(crate) toml: String,
+}
+
+impl AdminConfig for Config {
+    /// Load the configuration, the storage schema must be migrated to the current version
+    fn load(env: &Env) -> Result<Config, Error> {
+        Schema::require_current(env)?;
+        env.storage()
+            .instance()
+            .get(&CONFIG)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn save(&self, env: &Env) {
+        env.storage().instance().set(&CONFIG, self);
+    }
+
+    fn admin(&self) -> &Address {
+        &self.admin
+    }
+}
+
+impl Config {
+    /// Move the configuration from separate persistent entries into the instance storage
+    pub(crate) fn migrate(env: &Env) -> Result<(), Error> {
+        let storage = env.storage().persistent();
+        let id: String = storage.get(&ID).ok_or(Error::NotInitialized)?;
+        let admin: Address = storage.get(&ADMIN).ok_or(Error::NotInitialized)?;
+        let empty = String::from_str(env, "");
+        let config = Config {
+            id,
+            admin,
+            name: storage.get(&NAME).unwrap_or(empty.clone()),
+            description: storage.get(&DESCRIPTION).unwrap_or(empty.clone()),
+            budget: storage.get(&BUDGET).unwrap_or(0),
+            toml: storage.get(&TOML).unwrap_or(empty),
+        };
+        for key in [ID, ADMIN, NAME, DESCRIPTION, BUDGET, TOML] {
+            if storage.has(&key) {
+                storage.remove(&key);
+            }
+        }
+        config.save(env);
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,9 +1,16 @@
-use crate::store::StorageKey::{Media, MediaBlock};
-use crate::store::{
-    MediaInfo, MediaType, ADMIN, DESCRIPTION, ID, MEDIA_LIST, NAME, BUDGET, TOML,
-};
+use crate::config::Config;
+use crate::schema::Schema;
+use crate::escrow::BudgetEscrow;
+use crate::store::{Escrow, Metadata, Selection};
 use crate::upgrade::UpgradeableContract;
-use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, String, Vec};
+use marketplace_asset::admin::AdminConfig;
+use marketplace_asset::auth::Signers;
+use marketplace_asset::media::{MediaStore, MAX_BLOCK_SIZE};
+use marketplace_asset::metadata::{MediaInfo, MediaKey, MediaRef, MediaType};
+use marketplace_asset::ttl::Ttl;
+use marketplace_asset::upgrade::{PendingUpgrade, Upgradeable};
+use marketplace_asset::Error;
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, String, Vec};
 
 // GCZMWP4RXU2QQV4ASC3PF6K4URGMBES4UR5GS2QYDUACL5SDSQWTDX52
 // SAOASHP7NFQ3YO5AMYC6QVI5HOAWCIFUXVEZYFETIC4KSLZSRVXXMZU7
@@ -21,6 +28,7 @@ use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, String, Vec
 /// Name
 /// Description
 /// Budget
+/// Escrow of the budget
 /// Amount
 /// Media
 /// Toml file link
@@ -40,121 +48,284 @@ impl DemandContract {
         budget: u64,
         toml_file_link: String,
     ) {
-        // Set ID for smart contract
-        e.storage().persistent().set(&ID, &id);
-
-        Self::set_admin(e.clone(), admin);
-        Self::set_name(e.clone(), name);
-        Self::set_description(e.clone(), description);
-        Self::set_budget(e.clone(), budget);
-        Self::set_toml_file(e, toml_file_link);
-    }
-    fn authorize_admin(env: &Env) {
-        if let Some(admin) = env.storage().persistent().get::<_, Address>(&ADMIN) {
-            admin.require_auth();
+        Config {
+            id,
+            admin,
+            name,
+            description,
+            budget,
+            toml: toml_file_link,
         }
+        .save(&e);
+        Schema::init(&e);
+        Ttl::extend_instance(&e);
+    }
+    fn authorize_admin(env: &Env) -> Config {
+        Config::require_admin(env)
+    }
+    fn load_config(env: &Env) -> Config {
+        Config::load(env).unwrap_or_else(|error| panic_with_error!(env, error))
     }
     pub fn set_admin(env: Env, admin: Address) {
-        Self::authorize_admin(&env);
-        env.storage().persistent().set(&ADMIN, &admin);
+        let mut config = Self::authorize_admin(&env);
+        config.admin = admin;
+        config.save(&env);
     }
 
     pub fn set_name(env: Env, name: String) {
-        Self::authorize_admin(&env);
-        env.storage().persistent().set(&NAME, &name);
+        let mut config = Self::authorize_admin(&env);
+        config.name = name;
+        config.save(&env);
     }
 
     pub fn set_description(env: Env, description: String) {
-        Self::authorize_admin(&env);
-        env.storage().persistent().set(&DESCRIPTION, &description);
+        let mut config = Self::authorize_admin(&env);
+        config.description = description;
+        config.save(&env);
     }
 
+    /// Set the budget, it can't be less than the budget funded by the buyer
     pub fn set_budget(env: Env, budget: u64) {
+        let mut config = Self::authorize_admin(&env);
+        BudgetEscrow::set_budget(&env, &mut config, budget);
+        config.save(&env);
+    }
+
+    /// Set the asset the budget is paid in, it can't be changed after the escrow is funded
+    pub fn set_pay_asset(env: Env, asset: Address) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&BUDGET, &budget);
+        BudgetEscrow::set_pay_asset(&env, asset);
+    }
+
+    /// Lock the 'amount' of the pay asset from the admin in the smart contract up to the budget
+    pub fn fund(env: Env, amount: i128) {
+        let config = Self::authorize_admin(&env);
+        BudgetEscrow::fund(&env, &config, amount);
+    }
+
+    /// Return the 'amount' of unused budget to the admin
+    pub fn withdraw(env: Env, amount: i128) {
+        let config = Self::authorize_admin(&env);
+        BudgetEscrow::withdraw(&env, &config, amount);
+    }
+
+    /// Hold the price of the 'quantity' of goods for its seller until the delivery
+    pub fn select_goods(env: Env, goods: Address, quantity: i128) {
+        Self::authorize_admin(&env);
+        BudgetEscrow::select_goods(&env, goods, quantity);
+    }
+
+    /// Cancel the selected goods with the consent of the seller, the held amount can be withdrawn again
+    pub fn cancel_selection(env: Env) {
+        Self::authorize_admin(&env);
+        BudgetEscrow::cancel_selection(&env);
+    }
+
+    /// Confirm the delivery of the selected goods and pay the held amount to the seller
+    pub fn release(env: Env) {
+        Self::authorize_admin(&env);
+        BudgetEscrow::release(&env);
     }
 
     pub fn media_max_block_size() -> u64 {
-        2 << 16
+        MAX_BLOCK_SIZE as u64
     }
 
+    /// Add the media of total_blocks blocks. media_hash is SHA-256 of the concatenated block
+    /// hashes, of the media itself when it has no blocks or the content hash of media_ref
     pub fn add_media(
         env: Env,
         media_id: String,
         media_type: MediaType,
         media: String,
         total_blocks: u64,
+        media_hash: BytesN<32>,
+        media_ref: Option<MediaRef>,
     ) {
         Self::authorize_admin(&env);
-        if env.storage().persistent().has(&MEDIA_LIST) {
-            let mut media_list: Vec<String> = env.storage().persistent().get(&MEDIA_LIST).unwrap();
-            media_list.push_back(media_id.clone());
-            env.storage().persistent().set(&MEDIA_LIST, &media_list);
-        } else {
-            let media_list: Vec<String> = vec![&env, media_id.clone()];
-            env.storage().persistent().set(&MEDIA_LIST, &media_list);
-        }
-        let media_info = MediaInfo {
-            media_id: media_id.clone(),
+        MediaStore::add(
+            &env,
+            media_id,
             media_type,
-            total_blocks,
             media,
-        };
-        env.storage()
-            .persistent()
-            .set(&Media(media_id), &media_info);
+            total_blocks,
+            media_hash,
+            media_ref,
+        );
     }
-    pub fn upload_media_block(env: Env, media_id: String, media: String, block_number: u64) {
+    pub fn upload_media_block(
+        env: Env,
+        media_id: String,
+        media: Bytes,
+        block_number: u64,
+        block_hash: BytesN<32>,
+    ) {
         Self::authorize_admin(&env);
-        env.storage()
-            .persistent()
-            .set(&MediaBlock(media_id, block_number), &media)
+        MediaStore::upload_block(&env, media_id, block_number, media, block_hash);
     }
-    fn remove_media_block(env: Env, media_id: String, block_number: u64) {
-        env.storage()
-            .persistent()
-            .remove(&MediaBlock(media_id, block_number))
+    pub fn remove_media_block(env: Env, media_id: String, block_number: u64) {
+        Self::authorize_admin(&env);
+        MediaStore::remove_block(&env, media_id, block_number);
     }
     pub fn remove_media(env: Env, media_id: String) {
         Self::authorize_admin(&env);
-        if env.storage().persistent().has(&Media(media_id.clone())) {
-            let media_info: MediaInfo = env
-                .storage()
-                .persistent()
-                .get(&Media(media_id.clone()))
-                .unwrap();
-            for block in 1..=media_info.total_blocks {
-                Self::remove_media_block(env.clone(), media_id.clone(), block);
-            }
-            if env.storage().persistent().has(&MEDIA_LIST) {
-                let mut media_list: Vec<String> =
-                    env.storage().persistent().get(&MEDIA_LIST).unwrap();
-                let index = media_list.first_index_of(&media_id);
-                if !index.clone().is_none() {
-                    media_list.remove(index.unwrap());
-                }
-                env.storage().persistent().set(&MEDIA_LIST, &media_list);
-            }
-            env.storage().persistent().remove(&Media(media_id.clone()));
-        }
+        MediaStore::remove(&env, media_id);
+    }
+    /// Return true if all blocks of the media are uploaded and match the media hash
+    pub fn is_media_complete(env: Env, media_id: String) -> bool {
+        MediaStore::is_complete(&env, &media_id)
     }
 
     pub fn set_toml_file(env: Env, toml_file_link: String) {
+        let mut config = Self::authorize_admin(&env);
+        config.toml = toml_file_link;
+        config.save(&env);
+    }
+
+    /// Return the demand description with the media list
+    pub fn metadata(env: Env) -> Metadata {
+        let config = Self::load_config(&env);
+        Metadata {
+            id: config.id,
+            admin: config.admin,
+            name: config.name,
+            description: config.description,
+            budget: config.budget,
+            toml: config.toml,
+            media_list: MediaStore::media_list(&env),
+        }
+    }
+
+    pub fn id(env: Env) -> String {
+        Self::load_config(&env).id
+    }
+
+    pub fn admin(env: Env) -> Address {
+        Self::load_config(&env).admin
+    }
+
+    pub fn name(env: Env) -> String {
+        Self::load_config(&env).name
+    }
+
+    pub fn description(env: Env) -> String {
+        Self::load_config(&env).description
+    }
+
+    pub fn budget(env: Env) -> u64 {
+        Self::load_config(&env).budget
+    }
+
+    /// Return the pay asset, the funded balance, the released amount and the selected goods
+    pub fn escrow(env: Env) -> Option<Escrow> {
+        Self::load_config(&env);
+        BudgetEscrow::escrow(&env)
+    }
+
+    /// Return the goods the budget is held for until the delivery
+    pub fn selection(env: Env) -> Option<Selection> {
+        Self::load_config(&env);
+        BudgetEscrow::selection(&env)
+    }
+
+    pub fn toml_file(env: Env) -> String {
+        Self::load_config(&env).toml
+    }
+
+    pub fn media_list(env: Env) -> Vec<String> {
+        MediaStore::media_list(&env)
+    }
+
+    pub fn media(env: Env, media_id: String) -> MediaInfo {
+        MediaStore::media(&env, &media_id)
+    }
+
+    pub fn media_block(env: Env, media_id: String, block_number: u64) -> String {
+        MediaStore::media_block(&env, &media_id, block_number)
+    }
+
+    /// Return the media block as bytes, read blocks 1..=total_blocks to stream the media
+    pub fn media_chunk(env: Env, media_id: String, block_number: u64) -> Bytes {
+        MediaStore::media_chunk(&env, &media_id, block_number)
+    }
+
+    /// Return MIME type of the media
+    pub fn media_mime_type(env: Env, media_id: String) -> String {
+        MediaStore::mime_type(&env, &media_id)
+    }
+
+    pub fn media_ref(env: Env, media_id: String) -> MediaRef {
+        MediaStore::media_ref(&env, &media_id)
+    }
+
+    /// Replace the keys that sign for the demand contract as an account,
+    /// at least 'threshold' of them must sign, no keys remove the signers.
+    /// Signers can't spend the escrowed pay asset
+    pub fn set_signers(env: Env, keys: Vec<BytesN<32>>, threshold: u32) {
+        Self::authorize_admin(&env);
+        Signers::set(&env, keys, threshold);
+    }
+
+    /// return the keys that sign for the demand contract
+    pub fn signers(env: Env) -> Option<Signers> {
+        Signers::load(&env)
+    }
+
+    /// Extend time to live of the demand description, media and the given storage entries
+    pub fn extend_all(env: Env, keys: Vec<MediaKey>) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&TOML, &toml_file_link)
+        Ttl::extend_all(&env, keys);
     }
 
     /// return version description
     pub fn version_build(env: Env) -> String {
         UpgradeableContract::version_build(env)
     }
-    /// return timestemp of the build
+    /// return version of the code
     pub fn version() -> i32 {
         UpgradeableContract::version()
     }
 
-    /// Upgrade smart contract
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        UpgradeableContract::upgrade(env, new_wasm_hash)
+    /// return storage schema version of the smart contract
+    pub fn schema_version(env: Env) -> u32 {
+        Schema::version(&env)
+    }
+
+    /// Migrate the storage to the schema version of the code after upgrade,
+    /// other calls fail with SchemaOutdated until the migration is done
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        Schema::migrate(&env)
+    }
+
+    /// return minimal delay between scheduling and execution of the upgrade in seconds
+    pub fn upgrade_delay(env: Env) -> u64 {
+        UpgradeableContract::upgrade_delay(&env)
+    }
+
+    /// Set minimal delay between scheduling and execution of the upgrade in seconds,
+    /// a shorter delay applies after the current one
+    pub fn set_upgrade_delay(env: Env, delay: u64) {
+        UpgradeableContract::set_upgrade_delay(env, delay)
+    }
+
+    /// return the upgrade scheduled by the admin
+    pub fn pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        UpgradeableContract::pending_upgrade(env)
+    }
+
+    /// Schedule upgrade of smart contract to the 'hash' of installed code,
+    /// the 'eta' ledger timestamp must be at least the upgrade delay ahead
+    pub fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64) {
+        UpgradeableContract::schedule_upgrade(env, hash, eta)
+    }
+
+    /// Cancel the scheduled upgrade
+    pub fn cancel_upgrade(env: Env) {
+        UpgradeableContract::cancel_upgrade(env)
+    }
+
+    /// Upgrade smart contract after the scheduled time, call 'migrate' after the upgrade
+    pub fn execute_upgrade(env: Env) {
+        UpgradeableContract::execute_upgrade(env)
     }
 }
@@ -1,14 +1,16 @@
 use soroban_sdk::contracterror;
 
+/// Errors of the demand smart contract, other codes are used by marketplace_asset::Error
 #[contracterror]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u32)]
-pub enum Error {
-    AlreadyInitialized = 1,
-    BadSignatureOrder = 2,
-    NotEnoughSigners = 3,
-    InvalidContext = 4,
-    BadArgs = 5,
-    NotInitialized = 6,
-    UnknownSigner = 7,
-}
\ No newline at end of file
+pub enum DemandError {
+    NegativeAmount = 11,
+    BudgetExceeded = 12,
+    InsufficientEscrow = 13,
+    PayAssetNotSet = 14,
+    EscrowNotEmpty = 15,
+    GoodsNotSelected = 16,
+    GoodsSelected = 17,
+    AssetMismatch = 18,
+}
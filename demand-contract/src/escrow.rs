@@ -0,0 +1,178 @@
+use crate::config::Config;
+use crate::error::DemandError;
+use crate::store::{Escrow, Selection, ESCROW, SELECTION};
+use contract_types::goods::GoodsClient;
+use marketplace_asset::Error;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{panic_with_error, symbol_short, Address, Env};
+
+// Escrow of the budget:
+// - the buyer is the admin of the demand, it locks the pay asset up to the budget
+// - committed is the balance locked in the smart contract and the amount released to sellers
+// - the amount of the selected goods is held until the buyer confirms the delivery,
+//   it returns to the balance only if the seller agrees to cancel the selection
+// - the rest of the balance can be withdrawn by the buyer
+
+pub struct BudgetEscrow;
+
+impl BudgetEscrow {
+    pub fn escrow(env: &Env) -> Option<Escrow> {
+        env.storage().instance().get(&ESCROW)
+    }
+
+    fn load(env: &Env) -> Escrow {
+        Self::escrow(env).unwrap_or_else(|| panic_with_error!(env, DemandError::PayAssetNotSet))
+    }
+
+    fn save(env: &Env, escrow: &Escrow) {
+        env.storage().instance().set(&ESCROW, escrow);
+    }
+
+    pub fn selection(env: &Env) -> Option<Selection> {
+        env.storage().instance().get(&SELECTION)
+    }
+
+    fn take_selection(env: &Env) -> Selection {
+        let selection = Self::selection(env)
+            .unwrap_or_else(|| panic_with_error!(env, DemandError::GoodsNotSelected));
+        env.storage().instance().remove(&SELECTION);
+        selection
+    }
+
+    fn check_amount(env: &Env, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(env, DemandError::NegativeAmount);
+        }
+    }
+
+    pub fn committed(env: &Env) -> i128 {
+        Self::escrow(env)
+            .map(|escrow| escrow.balance + escrow.released)
+            .unwrap_or(0)
+    }
+
+    /// Set the budget, it can't be less than the budget already funded
+    pub fn set_budget(env: &Env, config: &mut Config, budget: u64) {
+        if (budget as i128) < Self::committed(env) {
+            panic_with_error!(env, DemandError::BudgetExceeded);
+        }
+        config.budget = budget;
+    }
+
+    /// Set the asset the budget is paid in, it can be changed until the escrow is funded
+    pub fn set_pay_asset(env: &Env, asset: Address) {
+        if Self::selection(env).is_some() {
+            panic_with_error!(env, DemandError::GoodsSelected);
+        }
+        if Self::committed(env) != 0 {
+            panic_with_error!(env, DemandError::EscrowNotEmpty);
+        }
+        Self::save(
+            env,
+            &Escrow {
+                asset: asset.clone(),
+                balance: 0,
+                released: 0,
+            },
+        );
+        env.events().publish((symbol_short!("pay_asset"),), asset);
+    }
+
+    /// Lock the 'amount' of the pay asset from the buyer, the committed amount can't exceed the budget
+    pub fn fund(env: &Env, config: &Config, amount: i128) {
+        Self::check_amount(env, amount);
+        let mut escrow = Self::load(env);
+        let committed = escrow.balance + escrow.released;
+        if committed + amount > config.budget as i128 {
+            panic_with_error!(env, DemandError::BudgetExceeded);
+        }
+        TokenClient::new(env, &escrow.asset).transfer(
+            &config.admin,
+            &env.current_contract_address(),
+            &amount,
+        );
+        escrow.balance += amount;
+        Self::save(env, &escrow);
+        env.events()
+            .publish((symbol_short!("fund"), config.admin.clone()), amount);
+    }
+
+    /// Return the 'amount' of the balance not held for the selected goods to the buyer
+    pub fn withdraw(env: &Env, config: &Config, amount: i128) {
+        Self::check_amount(env, amount);
+        let mut escrow = Self::load(env);
+        let held = Self::selection(env)
+            .map(|selection| selection.amount)
+            .unwrap_or(0);
+        if escrow.balance - held < amount {
+            panic_with_error!(env, DemandError::InsufficientEscrow);
+        }
+        TokenClient::new(env, &escrow.asset).transfer(
+            &env.current_contract_address(),
+            &config.admin,
+            &amount,
+        );
+        escrow.balance -= amount;
+        Self::save(env, &escrow);
+        env.events()
+            .publish((symbol_short!("withdraw"), config.admin.clone()), amount);
+    }
+
+    /// Hold the price of the 'quantity' of goods quoted by the goods smart contract,
+    /// the seller is the admin of the goods
+    pub fn select_goods(env: &Env, goods: Address, quantity: i128) {
+        Self::check_amount(env, quantity);
+        let escrow = Self::load(env);
+        if Self::selection(env).is_some() {
+            panic_with_error!(env, DemandError::GoodsSelected);
+        }
+        let price = GoodsClient::new(env, &goods).quote(&quantity, &None);
+        if price.asset != escrow.asset {
+            panic_with_error!(env, DemandError::AssetMismatch);
+        }
+        if price.amount <= 0 {
+            panic_with_error!(env, Error::BadArgs);
+        }
+        if escrow.balance < price.amount {
+            panic_with_error!(env, DemandError::InsufficientEscrow);
+        }
+        let seller = StellarAssetClient::new(env, &goods).admin();
+        env.events().publish(
+            (symbol_short!("select"), goods.clone()),
+            (seller.clone(), quantity, price.amount),
+        );
+        let selection = Selection {
+            goods,
+            seller,
+            quantity,
+            amount: price.amount,
+        };
+        env.storage().instance().set(&SELECTION, &selection);
+    }
+
+    /// Return the amount held for the selected goods to the balance, the seller must agree
+    pub fn cancel_selection(env: &Env) {
+        let selection = Self::take_selection(env);
+        selection.seller.require_auth();
+        env.events()
+            .publish((symbol_short!("unselect"), selection.goods), selection.amount);
+    }
+
+    /// Pay the amount held for the selected goods to the seller on delivery
+    pub fn release(env: &Env) {
+        let mut escrow = Self::load(env);
+        let selection = Self::take_selection(env);
+        escrow.balance -= selection.amount;
+        escrow.released += selection.amount;
+        Self::save(env, &escrow);
+        TokenClient::new(env, &escrow.asset).transfer(
+            &env.current_contract_address(),
+            &selection.seller,
+            &selection.amount,
+        );
+        env.events().publish(
+            (symbol_short!("release"), selection.seller),
+            (selection.goods, selection.amount),
+        );
+    }
+}
@@ -2,6 +2,13 @@
 
 mod contract;
 mod store;
-mod error;
 mod upgrade;
 mod auth;
+mod config;
+mod schema;
+mod error;
+mod escrow;
+
+pub use crate::contract::{DemandContract, DemandContractClient};
+pub use crate::error::DemandError;
+pub use crate::store::{Escrow, Selection};
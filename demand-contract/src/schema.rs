@@ -0,0 +1,76 @@
+use soroban_sdk::{Address, Env};
+use crate::config::Config;
+use marketplace_asset::Error;
+use crate::store::{ADMIN, CONFIG, SCHEMA};
+
+// Storage schema versions:
+// 1 - demand description is kept in separate persistent entries
+// 2 - demand description is kept in the instance storage as a Config
+
+/// Version of the storage schema the code works with
+pub(crate) const SCHEMA_VERSION: u32 = 2;
+
+pub struct Schema;
+
+impl Schema {
+    /// Return the version of the stored schema,
+    /// smart contracts deployed before the version was stored are detected by their layout
+    pub fn version(env: &Env) -> u32 {
+        if let Some(version) = env.storage().instance().get(&SCHEMA) {
+            return version;
+        }
+        if env.storage().instance().has(&CONFIG) {
+            return 2;
+        }
+        if env.storage().persistent().has(&ADMIN) {
+            return 1;
+        }
+        SCHEMA_VERSION
+    }
+
+    /// Store the current schema version for the new smart contract
+    pub fn init(env: &Env) {
+        env.storage().instance().set(&SCHEMA, &SCHEMA_VERSION);
+    }
+
+    /// Refuse to operate until the stored schema is migrated to the version of the code
+    pub fn require_current(env: &Env) -> Result<(), Error> {
+        if Self::version(env) < SCHEMA_VERSION {
+            return Err(Error::SchemaOutdated);
+        }
+        Ok(())
+    }
+
+    /// Return the admin address stored in the given schema version
+    pub fn admin(env: &Env, version: u32) -> Result<Address, Error> {
+        if version < 2 {
+            return env
+                .storage()
+                .persistent()
+                .get(&ADMIN)
+                .ok_or(Error::NotInitialized);
+        }
+        env.storage()
+            .instance()
+            .get::<_, Config>(&CONFIG)
+            .map(|config| config.admin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Run migration steps from the stored schema version to the version of the code,
+    /// each step upgrades the schema by one version. Return the migrated version
+    pub fn migrate(env: &Env) -> Result<u32, Error> {
+        let mut version = Self::version(env);
+        Self::admin(env, version)?.require_auth();
+
+        while version < SCHEMA_VERSION {
+            match version {
+                1 => Config::migrate(env)?,
+                _ => return Err(Error::SchemaOutdated),
+            }
+            version += 1;
+        }
+        env.storage().instance().set(&SCHEMA, &version);
+        Ok(version)
+    }
+}
\ No newline at end of file
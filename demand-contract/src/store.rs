@@ -1,47 +1,78 @@
-use soroban_sdk::{contracttype, symbol_short, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, String, Symbol, Vec};
 
-#[contracttype]
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum MediaType {
-    ImageBase64 = 1,
-    ImageLink = 2,
-    VideoLink = 4,
-}
+// Storage placement:
+// - instance storage keeps the smart contract instance, its code, the demand description
+//   and the escrow of the budget alive
+// - persistent storage keeps the media,
+//   entries are extended when the admin changes them and can be extended explicitly
+// - temporary storage is not used
+// Media and upgrade keys are defined by marketplace_asset
+
+/// Config is the demand description in the instance storage. Value is a Config
+pub(crate) const CONFIG: Symbol = symbol_short!("Config");
+
+/// Schema is a version of the storage schema. Value is an u32
+pub(crate) const SCHEMA: Symbol = symbol_short!("Schema");
+
+/// Escrow is the budget locked by the buyer in the pay asset. Value is an Escrow
+pub const ESCROW: Symbol = symbol_short!("Escrow");
+
+/// Selection is the goods the budget is held for until the delivery. Value is a Selection
+pub const SELECTION: Symbol = symbol_short!("Selection");
+
+// The demand description was kept in separate persistent entries before it was moved
+// into the instance storage, these keys are read only to migrate deployed smart contracts
 
 /// ID is a unique id of the demand. Value is a String
-pub(crate) const ID: Symbol = symbol_short!("id");
+pub const ID: Symbol = symbol_short!("id");
 
 /// Admin is an admin address to manage the smart contract of the demand. Value is an Address
-pub(crate) const ADMIN: Symbol = symbol_short!("Admin");
+pub const ADMIN: Symbol = symbol_short!("Admin");
 
 /// Name is a name of a demand. Value is a String
-pub(crate) const NAME: Symbol = symbol_short!("Name");
+pub const NAME: Symbol = symbol_short!("Name");
 
 /// Description is a description of a demand. Value is a String
-pub(crate) const DESCRIPTION: Symbol = symbol_short!("Desc");
+pub const DESCRIPTION: Symbol = symbol_short!("Desc");
 
 /// Budget is a current budget of a demand. Value is a f64
-pub(crate) const BUDGET: Symbol = symbol_short!("Budget");
-
-/// MediaList is a list of media_id added to the smart contract. Value is an array of string
-pub(crate) const MEDIA_LIST: Symbol = symbol_short!("MediaList");
+pub const BUDGET: Symbol = symbol_short!("Budget");
 
 /// Toml is representation of toml file link. Value is String.
-pub(crate) const TOML: Symbol = symbol_short!("toml");
+pub const TOML: Symbol = symbol_short!("toml");
 
+/// Metadata is the demand description for portals
 #[contracttype]
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct MediaInfo {
-    pub(crate) media_id: String,
-    pub(crate) media_type: MediaType,
-    pub(crate) total_blocks: u64,
-    pub(crate) media: String,
+pub struct Metadata {
+    pub id: String,
+    pub admin: Address,
+    pub name: String,
+    pub description: String,
+    pub budget: u64,
+    pub toml: String,
+    pub media_list: Vec<String>,
 }
 
+/// Escrow is the budget locked by the buyer, the admin of the demand
 #[contracttype]
-pub enum StorageKey {
-    /// Media is representation of a media data. Value is MediaInfo.
-    Media(String),
-    /// MediaBlock is representation of a media data block as a base64 string. Value is String.
-    MediaBlock(String, u64),
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Escrow {
+    /// asset the budget is paid in
+    pub asset: Address,
+    /// amount locked in the smart contract
+    pub balance: i128,
+    /// amount paid to sellers
+    pub released: i128,
+}
+
+/// Selection is the goods chosen by the buyer, the amount is held until the delivery
+/// and can be returned to the balance only with the consent of the seller
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Selection {
+    pub goods: Address,
+    pub seller: Address,
+    pub quantity: i128,
+    pub amount: i128,
 }
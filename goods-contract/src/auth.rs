@@ -1,19 +1,13 @@
-use crate::contract::GoodsContract;
-use crate::error::Error;
+use crate::contract::{GoodsContract, GoodsContractArgs, GoodsContractClient};
+use marketplace_asset::auth::{authenticate, AccSignature};
+use marketplace_asset::Error;
 use soroban_sdk::{
     auth::{Context, CustomAccountInterface},
-    contracttype,
     crypto::Hash,
-    BytesN, Env, Vec,
+    contractimpl, Env, Vec,
 };
 
-#[contracttype]
-#[derive(Clone)]
-pub struct AccSignature {
-    pub public_key: BytesN<32>,
-    pub signature: BytesN<64>,
-}
-
+#[contractimpl]
 impl CustomAccountInterface for GoodsContract {
     type Signature = Vec<AccSignature>;
     type Error = Error;
@@ -47,32 +41,10 @@ impl CustomAccountInterface for GoodsContract {
         env: Env,
         signature_payload: Hash<32>,
         signatures: Vec<AccSignature>,
-        _: Vec<Context>,
+        _auth_context: Vec<Context>,
     ) -> Result<(), Error> {
         // Perform authentication.
         authenticate(&env, &signature_payload, &signatures)?;
         Ok(())
     }
 }
-
-fn authenticate(
-    env: &Env,
-    signature_payload: &Hash<32>,
-    signatures: &Vec<AccSignature>,
-) -> Result<(), Error> {
-    for i in 0..signatures.len() {
-        let signature = signatures.get_unchecked(i);
-        if i > 0 {
-            let prev_signature = signatures.get_unchecked(i - 1);
-            if prev_signature.public_key >= signature.public_key {
-                return Err(Error::BadSignatureOrder);
-            }
-        }
-        env.crypto().ed25519_verify(
-            &signature.public_key,
-            &signature_payload.clone().into(),
-            &signature.signature,
-        );
-    }
-    Ok(())
-}
@@ -0,0 +1,101 @@
+use soroban_sdk::{contracttype, Address, Env, String};
+use marketplace_asset::admin::AdminConfig;
+use marketplace_asset::Error;
+use crate::pricing::Pricing;
+use crate::schema::Schema;
+use crate::store::{ADMIN, DESCRIPTION, ID, NAME, PRICE, AMOUNT, TOML, CONFIG};
+
+/// Config is the goods description kept in the instance storage
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    /// unique id of the goods
+    pub(crate) id: String,
+    /// admin address to manage the smart contract
+    pub(crate) admin: Address,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    /// available amount of goods
+    pub(crate) amount: u64,
+    /// link to the toml file
+    pub(crate) toml: String,
+}
+
+/// ConfigV2 is the goods description of the schema version 2, the price had no currency
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConfigV2 {
+    pub(crate) id: String,
+    pub(crate) admin: Address,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) price: u64,
+    pub(crate) amount: u64,
+    pub(crate) toml: String,
+}
+
+impl AdminConfig for Config {
+    /// Load the configuration, the storage schema must be migrated to the current version
+    fn load(env: &Env) -> Result<Config, Error> {
+        Schema::require_current(env)?;
+        env.storage()
+            .instance()
+            .get(&CONFIG)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn save(&self, env: &Env) {
+        env.storage().instance().set(&CONFIG, self);
+    }
+
+    fn admin(&self) -> &Address {
+        &self.admin
+    }
+}
+
+impl Config {
+    /// Move the configuration from separate persistent entries into the instance storage
+    pub(crate) fn migrate(env: &Env) -> Result<(), Error> {
+        let storage = env.storage().persistent();
+        let id: String = storage.get(&ID).ok_or(Error::NotInitialized)?;
+        let admin: Address = storage.get(&ADMIN).ok_or(Error::NotInitialized)?;
+        let empty = String::from_str(env, "");
+        let config = ConfigV2 {
+            id,
+            admin,
+            name: storage.get(&NAME).unwrap_or(empty.clone()),
+            description: storage.get(&DESCRIPTION).unwrap_or(empty.clone()),
+            price: storage.get(&PRICE).unwrap_or(0),
+            amount: storage.get(&AMOUNT).unwrap_or(0),
+            toml: storage.get(&TOML).unwrap_or(empty),
+        };
+        for key in [ID, ADMIN, NAME, DESCRIPTION, PRICE, AMOUNT, TOML] {
+            if storage.has(&key) {
+                storage.remove(&key);
+            }
+        }
+        env.storage().instance().set(&CONFIG, &config);
+        Ok(())
+    }
+
+    /// Move the price without currency from the configuration to the price history,
+    /// the admin sets a new price with the asset and decimals after the migration
+    pub(crate) fn migrate_price(env: &Env) -> Result<(), Error> {
+        let legacy: ConfigV2 = env
+            .storage()
+            .instance()
+            .get(&CONFIG)
+            .ok_or(Error::NotInitialized)?;
+        Pricing::migrate_legacy(env, legacy.price);
+        let config = Config {
+            id: legacy.id,
+            admin: legacy.admin,
+            name: legacy.name,
+            description: legacy.description,
+            amount: legacy.amount,
+            toml: legacy.toml,
+        };
+        config.save(env);
+        Ok(())
+    }
+}
\ No newline at end of file
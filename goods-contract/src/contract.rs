@@ -1,10 +1,21 @@
-use crate::store::StorageKey::{Media, MediaBlock};
-use crate::store::{
-    MediaInfo, MediaType, ADMIN, AMOUNT, DESCRIPTION, ID, MEDIA_LIST, NAME, PRICE, TOML,
-};
+use crate::config::Config;
+use crate::inventory::Inventory;
+use crate::schema::Schema;
+use crate::pricing::Pricing;
+use crate::token::GoodsToken;
 use crate::upgrade::UpgradeableContract;
-use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, String, Vec};
-use soroban_sdk::token::StellarAssetInterface;
+use contract_types::goods::{
+    GoodsInterface, Metadata, Price, PriceChange, PriceTier, Reservation, StorageKey,
+};
+use marketplace_asset::admin::AdminConfig;
+use marketplace_asset::auth::Signers;
+use marketplace_asset::media::{MediaStore, MAX_BLOCK_SIZE};
+use marketplace_asset::metadata::{MediaInfo, MediaRef, MediaType};
+use marketplace_asset::ttl::Ttl;
+use marketplace_asset::upgrade::{PendingUpgrade, Upgradeable};
+use marketplace_asset::Error;
+use soroban_sdk::{contract, contractimpl, panic_with_error, vec, Address, Bytes, BytesN, Env, String, Vec};
+use soroban_sdk::token::{StellarAssetInterface, TokenInterface};
 
 /// Admin
 /// Partner
@@ -18,206 +29,399 @@ use soroban_sdk::token::StellarAssetInterface;
 
 #[contract]
 pub struct GoodsContract;
-trait GoodsInterface : StellarAssetInterface {
-    fn __constructor(
-        e: Env,
-        admin: Address,
-        id: String,
-        name: String,
-        description: String,
-        price: u64,
-        amount: u64,
-        toml_file_link: String,
-    );
-    fn set_name(env: Env, name: String);
-    fn set_description(env: Env, description: String);
-    fn set_price(env: Env, price: u64);
-    fn set_amount(env: Env, amount: u64);
-    fn media_max_block_size() -> u64;
-    fn add_media(
-        env: Env,
-        media_id: String,
-        media_type: MediaType,
-        media: String,
-        total_blocks: u64,
-    );
-    fn upload_media_block(env: Env, media_id: String, media: String, block_number: u64);
-    fn remove_media_block(env: Env, media_id: String, block_number: u64);
-    fn remove_media(env: Env, media_id: String);
-    fn set_toml_file(env: Env, toml_file_link: String);
-    #[doc=" return version description"]
-
-    fn version_build(env: Env) -> String;
-    #[doc=" return timestemp of the build"]
-
-    fn version() -> i32;
-    #[doc=" Upgrade smart contract"]
-
-    fn upgrade(env: Env, new_wasm_hash: BytesN<32> );
-}
 
 #[contractimpl]
 impl StellarAssetInterface for GoodsContract {
     fn set_admin(env: Env, new_admin: Address) {
-        Self::authorize_admin(&env);
-        env.storage().persistent().set(&ADMIN, &new_admin);
+        let mut config = Self::authorize_admin(&env);
+        config.admin = new_admin;
+        config.save(&env);
     }
 
     fn admin(env: Env) -> Address {
-        todo!()
+        Self::load_config(&env).admin
     }
 
     fn set_authorized(env: Env, id: Address, authorize: bool) {
-        todo!()
+        let config = Self::authorize_admin(&env);
+        GoodsToken::set_authorized(&env, &config, &id, authorize);
     }
 
     fn authorized(env: Env, id: Address) -> bool {
-        todo!()
+        GoodsToken::authorized(&env, &id)
     }
 
     fn mint(env: Env, to: Address, amount: i128) {
-        todo!()
+        let config = Self::authorize_admin(&env);
+        GoodsToken::mint(&env, &config, &to, amount);
     }
 
     fn clawback(env: Env, from: Address, amount: i128) {
-        todo!()
+        let config = Self::authorize_admin(&env);
+        GoodsToken::clawback(&env, &config, &from, amount);
     }
 }
 
 #[contractimpl]
-impl GoodsContract {
-    fn authorize_admin(env: &Env) {
-        if let Some(admin) = env.storage().persistent().get::<_, Address>(&ADMIN) {
-            admin.require_auth();
-        }
+impl TokenInterface for GoodsContract {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        GoodsToken::allowance(&env, &from, &spender)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        Ttl::extend_instance(&env);
+        GoodsToken::approve(&env, &from, &spender, amount, expiration_ledger);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        GoodsToken::balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Ttl::extend_instance(&env);
+        GoodsToken::transfer(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        Ttl::extend_instance(&env);
+        GoodsToken::transfer_from(&env, &spender, &from, &to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        Ttl::extend_instance(&env);
+        GoodsToken::burn(&env, &from, amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        Ttl::extend_instance(&env);
+        GoodsToken::burn_from(&env, &spender, &from, amount);
+    }
+
+    fn decimals(_env: Env) -> u32 {
+        GoodsToken::decimals()
+    }
+
+    fn name(env: Env) -> String {
+        GoodsToken::name(&Self::load_config(&env))
+    }
+
+    fn symbol(env: Env) -> String {
+        GoodsToken::symbol(&Self::load_config(&env))
     }
 }
 
 #[contractimpl]
-impl GoodsInterface for GoodsContract {
+impl GoodsContract {
     /// Constructor requires Admin address
-    fn __constructor(
+    #[allow(clippy::too_many_arguments)]
+    pub fn __constructor(
         e: Env,
         admin: Address,
         id: String,
         name: String,
         description: String,
-        price: u64,
+        price: Price,
         amount: u64,
         toml_file_link: String,
     ) {
-        // Set ID for smart contract
-        e.storage().persistent().set(&ID, &id);
+        Config {
+            id,
+            admin,
+            name,
+            description,
+            amount,
+            toml: toml_file_link,
+        }
+        .save(&e);
+        Schema::init(&e);
+        Pricing::schedule(&e, price, vec![&e], e.ledger().timestamp());
+        Ttl::extend_instance(&e);
+    }
+
+    fn authorize_admin(env: &Env) -> Config {
+        Config::require_admin(env)
+    }
 
-        e.storage().persistent().set(&ADMIN, &admin);
-        e.storage().persistent().set(&NAME, &name);
-        e.storage().persistent().set(&DESCRIPTION, &description);
-        e.storage().persistent().set(&PRICE, &price);
-        e.storage().persistent().set(&PRICE, &price);
-        e.storage().persistent().set(&TOML, &toml_file_link)
+    fn load_config(env: &Env) -> Config {
+        Config::load(env).unwrap_or_else(|error| panic_with_error!(env, error))
     }
+}
 
+#[contractimpl]
+impl GoodsInterface for GoodsContract {
     fn set_name(env: Env, name: String) {
-        Self::authorize_admin(&env);
-        env.storage().persistent().set(&NAME, &name);
+        let mut config = Self::authorize_admin(&env);
+        config.name = name;
+        config.save(&env);
     }
 
     fn set_description(env: Env, description: String) {
+        let mut config = Self::authorize_admin(&env);
+        config.description = description;
+        config.save(&env);
+    }
+
+    /// Set the price and bulk price 'tiers' applied from now
+    fn set_price(env: Env, price: Price, tiers: Vec<PriceTier>) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&DESCRIPTION, &description);
+        Pricing::schedule(&env, price, tiers, env.ledger().timestamp());
     }
 
-    fn set_price(env: Env, price: u64) {
+    /// Schedule the price change at the 'effective_from' ledger timestamp,
+    /// a change scheduled at the same timestamp is replaced
+    fn schedule_price(env: Env, price: Price, tiers: Vec<PriceTier>, effective_from: u64) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&PRICE, &price);
+        Pricing::schedule(&env, price, tiers, effective_from);
     }
 
-    fn set_amount(env: Env, amount: u64) {
+    /// Cancel the price change scheduled at the 'effective_from' ledger timestamp
+    fn cancel_price(env: Env, effective_from: u64) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&AMOUNT, &amount);
+        Pricing::cancel(&env, effective_from);
+    }
+
+    fn price(env: Env) -> PriceChange {
+        Pricing::at(&env, env.ledger().timestamp())
+            .unwrap_or_else(|error| panic_with_error!(&env, error))
+    }
+
+    /// Return all price changes sorted by the effective timestamp,
+    /// buyers can verify the price at the time of purchase
+    fn price_history(env: Env) -> Vec<PriceChange> {
+        Pricing::history(&env)
+    }
+
+    /// Return the total price of the 'quantity' of goods with bulk tiers applied
+    /// at the 'timestamp', the current ledger timestamp is used if it isn't given
+    fn quote(env: Env, quantity: i128, timestamp: Option<u64>) -> Price {
+        let timestamp = timestamp.unwrap_or(env.ledger().timestamp());
+        Pricing::quote(&env, quantity, timestamp)
+            .unwrap_or_else(|error| panic_with_error!(&env, error))
+    }
+
+    /// Set the stock of goods, it can't be less than the goods sold as tokens or reserved
+    fn set_amount(env: Env, amount: u64) {
+        let mut config = Self::authorize_admin(&env);
+        Inventory::set_amount(&env, &mut config, amount);
+    }
+
+    /// Add goods to the stock
+    fn restock(env: Env, quantity: u64) {
+        let mut config = Self::authorize_admin(&env);
+        Inventory::restock(&env, &mut config, quantity);
+    }
+
+    /// Hold the 'quantity' of available goods for the 'buyer' until 'expires_at'
+    /// ledger timestamp, goods tokens minted to the buyer use the reservation first
+    fn reserve(env: Env, buyer: Address, quantity: i128, expires_at: u64) {
+        let config = Self::authorize_admin(&env);
+        Inventory::reserve(&env, &config, &buyer, quantity, expires_at);
+    }
+
+    /// Return reserved goods of the 'buyer' to the available stock,
+    /// anyone can release the expired reservation
+    fn release_reservation(env: Env, buyer: Address) {
+        Ttl::extend_instance(&env);
+        Inventory::release_reservation(&env, &buyer);
+    }
+
+    /// Deliver the 'quantity' of goods to the 'holder', its goods tokens are burned
+    fn fulfil(env: Env, holder: Address, quantity: i128) {
+        let mut config = Self::authorize_admin(&env);
+        Inventory::fulfil(&env, &mut config, &holder, quantity);
+    }
+
+    fn reservation(env: Env, buyer: Address) -> Option<Reservation> {
+        Inventory::reservation(&env, &buyer)
+    }
+
+    fn available(env: Env) -> i128 {
+        Inventory::available(&env, &Self::load_config(&env))
+    }
+
+    fn reserved(env: Env) -> i128 {
+        Inventory::reserved(&env)
+    }
+
+    fn sold(env: Env) -> i128 {
+        Inventory::sold(&env)
     }
 
     fn media_max_block_size() -> u64 {
-        2 << 16
+        MAX_BLOCK_SIZE as u64
     }
 
+    /// Add the media of total_blocks blocks. media_hash is SHA-256 of the concatenated block
+    /// hashes, of the media itself when it has no blocks or the content hash of media_ref
     fn add_media(
         env: Env,
         media_id: String,
         media_type: MediaType,
         media: String,
         total_blocks: u64,
+        media_hash: BytesN<32>,
+        media_ref: Option<MediaRef>,
     ) {
         Self::authorize_admin(&env);
-        if env.storage().persistent().has(&MEDIA_LIST) {
-            let mut media_list: Vec<String> = env.storage().persistent().get(&MEDIA_LIST).unwrap();
-            media_list.push_back(media_id.clone());
-            env.storage().persistent().set(&MEDIA_LIST, &media_list);
-        } else {
-            let media_list: Vec<String> = vec![&env, media_id.clone()];
-            env.storage().persistent().set(&MEDIA_LIST, &media_list);
-        }
-        let media_info = MediaInfo {
-            media_id: media_id.clone(),
+        MediaStore::add(
+            &env,
+            media_id,
             media_type,
-            total_blocks,
             media,
-        };
-        env.storage()
-            .persistent()
-            .set(&Media(media_id), &media_info);
+            total_blocks,
+            media_hash,
+            media_ref,
+        );
     }
-    fn upload_media_block(env: Env, media_id: String, media: String, block_number: u64) {
+    fn upload_media_block(
+        env: Env,
+        media_id: String,
+        media: Bytes,
+        block_number: u64,
+        block_hash: BytesN<32>,
+    ) {
         Self::authorize_admin(&env);
-        env.storage()
-            .persistent()
-            .set(&MediaBlock(media_id, block_number), &media)
+        MediaStore::upload_block(&env, media_id, block_number, media, block_hash);
     }
     fn remove_media_block(env: Env, media_id: String, block_number: u64) {
-        env.storage()
-            .persistent()
-            .remove(&MediaBlock(media_id, block_number))
+        Self::authorize_admin(&env);
+        MediaStore::remove_block(&env, media_id, block_number);
     }
     fn remove_media(env: Env, media_id: String) {
         Self::authorize_admin(&env);
-        if env.storage().persistent().has(&Media(media_id.clone())) {
-            let media_info: MediaInfo = env
-                .storage()
-                .persistent()
-                .get(&Media(media_id.clone()))
-                .unwrap();
-            for block in 1..=media_info.total_blocks {
-                Self::remove_media_block(env.clone(), media_id.clone(), block);
-            }
-            if env.storage().persistent().has(&MEDIA_LIST) {
-                let mut media_list: Vec<String> =
-                    env.storage().persistent().get(&MEDIA_LIST).unwrap();
-                let index = media_list.first_index_of(&media_id);
-                if !index.clone().is_none() {
-                    media_list.remove(index.unwrap());
-                }
-                env.storage().persistent().set(&MEDIA_LIST, &media_list);
-            }
-            env.storage().persistent().remove(&Media(media_id.clone()));
-        }
+        MediaStore::remove(&env, media_id);
+    }
+    fn is_media_complete(env: Env, media_id: String) -> bool {
+        MediaStore::is_complete(&env, &media_id)
     }
 
     fn set_toml_file(env: Env, toml_file_link: String) {
+        let mut config = Self::authorize_admin(&env);
+        config.toml = toml_file_link;
+        config.save(&env);
+    }
+
+    /// Return the goods description with the media list, the current price
+    /// is returned by 'price', the name is returned by 'name' of the goods token
+    fn metadata(env: Env) -> Metadata {
+        let config = Self::load_config(&env);
+        Metadata {
+            id: config.id,
+            admin: config.admin,
+            name: config.name,
+            description: config.description,
+            amount: config.amount,
+            toml: config.toml,
+            media_list: MediaStore::media_list(&env),
+        }
+    }
+
+    fn id(env: Env) -> String {
+        Self::load_config(&env).id
+    }
+
+    fn description(env: Env) -> String {
+        Self::load_config(&env).description
+    }
+
+    fn amount(env: Env) -> u64 {
+        Self::load_config(&env).amount
+    }
+
+    fn toml_file(env: Env) -> String {
+        Self::load_config(&env).toml
+    }
+
+    fn media_list(env: Env) -> Vec<String> {
+        MediaStore::media_list(&env)
+    }
+
+    fn media(env: Env, media_id: String) -> MediaInfo {
+        MediaStore::media(&env, &media_id)
+    }
+
+    fn media_block(env: Env, media_id: String, block_number: u64) -> String {
+        MediaStore::media_block(&env, &media_id, block_number)
+    }
+
+    fn media_chunk(env: Env, media_id: String, block_number: u64) -> Bytes {
+        MediaStore::media_chunk(&env, &media_id, block_number)
+    }
+
+    fn media_mime_type(env: Env, media_id: String) -> String {
+        MediaStore::mime_type(&env, &media_id)
+    }
+
+    fn media_ref(env: Env, media_id: String) -> MediaRef {
+        MediaStore::media_ref(&env, &media_id)
+    }
+
+    /// Replace the keys that sign for the goods contract as an account,
+    /// at least 'threshold' of them must sign, no keys remove the signers
+    fn set_signers(env: Env, keys: Vec<BytesN<32>>, threshold: u32) {
+        Self::authorize_admin(&env);
+        Signers::set(&env, keys, threshold);
+    }
+
+    fn signers(env: Env) -> Option<Signers> {
+        Signers::load(&env)
+    }
+
+    /// Extend time to live of the goods description, media and the given storage entries
+    fn extend_all(env: Env, keys: Vec<StorageKey>) {
         Self::authorize_admin(&env);
-        env.storage().persistent().set(&TOML, &toml_file_link)
+        Ttl::extend_all(&env, keys);
     }
 
     /// return version description
     fn version_build(env: Env) -> String {
         UpgradeableContract::version_build(env)
     }
-    /// return timestemp of the build
+    /// return version of the code
     fn version() -> i32 {
         UpgradeableContract::version()
     }
 
-    /// Upgrade smart contract
-    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        UpgradeableContract::upgrade(env, new_wasm_hash)
+    /// return storage schema version of the smart contract
+    fn schema_version(env: Env) -> u32 {
+        Schema::version(&env)
+    }
+
+    /// Migrate the storage to the schema version of the code after upgrade,
+    /// other calls fail with SchemaOutdated until the migration is done
+    fn migrate(env: Env) -> Result<u32, Error> {
+        Schema::migrate(&env)
+    }
+
+    /// return minimal delay between scheduling and execution of the upgrade in seconds
+    fn upgrade_delay(env: Env) -> u64 {
+        UpgradeableContract::upgrade_delay(&env)
+    }
+
+    /// Set minimal delay between scheduling and execution of the upgrade in seconds,
+    /// a shorter delay applies after the current one
+    fn set_upgrade_delay(env: Env, delay: u64) {
+        UpgradeableContract::set_upgrade_delay(env, delay)
+    }
+
+    /// return the upgrade scheduled by the admin
+    fn pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        UpgradeableContract::pending_upgrade(env)
+    }
+
+    /// Schedule upgrade of smart contract to the 'hash' of installed code,
+    /// the 'eta' ledger timestamp must be at least the upgrade delay ahead
+    fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64) {
+        UpgradeableContract::schedule_upgrade(env, hash, eta)
+    }
+
+    /// Cancel the scheduled upgrade
+    fn cancel_upgrade(env: Env) {
+        UpgradeableContract::cancel_upgrade(env)
+    }
+
+    /// Upgrade smart contract after the scheduled time, call 'migrate' after the upgrade
+    fn execute_upgrade(env: Env) {
+        UpgradeableContract::execute_upgrade(env)
     }
 }
@@ -1,14 +1,16 @@
 use soroban_sdk::contracterror;
 
+/// Errors of the goods smart contract, other codes are used by marketplace_asset::Error
 #[contracterror]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u32)]
-pub enum Error {
-    AlreadyInitialized = 1,
-    BadSignatureOrder = 2,
-    NotEnoughSigners = 3,
-    InvalidContext = 4,
-    BadArgs = 5,
-    NotInitialized = 6,
-    UnknownSigner = 7,
+pub enum GoodsError {
+    NegativeAmount = 11,
+    InsufficientBalance = 12,
+    InsufficientAllowance = 13,
+    NotAuthorized = 14,
+    AmountExceeded = 15,
+    ReservationNotFound = 16,
+    ReservationActive = 17,
+    PriceNotFound = 18,
 }
\ No newline at end of file
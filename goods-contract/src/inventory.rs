@@ -0,0 +1,153 @@
+use crate::config::Config;
+use crate::error::GoodsError;
+use crate::store::{RESERVED, SOLD};
+use crate::token::GoodsToken;
+use contract_types::goods::Reservation;
+use contract_types::goods::StorageKey::Reservation as ReservationKey;
+use marketplace_asset::admin::AdminConfig;
+use marketplace_asset::ttl::Ttl;
+use marketplace_asset::Error;
+use soroban_sdk::{panic_with_error, symbol_short, Address, Env};
+
+// Inventory of goods:
+// - amount is a stock of goods that are not delivered yet
+// - supply is a part of the stock sold as goods tokens
+// - reserved is a part of the stock held for buyers until their reservations expire
+// - available is the rest of the stock that can be reserved or minted
+
+pub struct Inventory;
+
+impl Inventory {
+    pub fn reserved(env: &Env) -> i128 {
+        env.storage().instance().get(&RESERVED).unwrap_or(0)
+    }
+
+    fn set_reserved(env: &Env, reserved: i128) {
+        env.storage().instance().set(&RESERVED, &reserved);
+    }
+
+    pub fn sold(env: &Env) -> i128 {
+        env.storage().instance().get(&SOLD).unwrap_or(0)
+    }
+
+    /// Expired reservations are counted as reserved until they are released
+    pub fn available(env: &Env, config: &Config) -> i128 {
+        config.amount as i128 - GoodsToken::supply(env) - Self::reserved(env)
+    }
+
+    pub fn reservation(env: &Env, buyer: &Address) -> Option<Reservation> {
+        let key = ReservationKey(buyer.clone());
+        Ttl::extend(env, &key);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Reservation of the 'buyer' that has not expired yet
+    pub fn active_reservation(env: &Env, buyer: &Address) -> Option<Reservation> {
+        Self::reservation(env, buyer)
+            .filter(|reservation| env.ledger().timestamp() <= reservation.expires_at)
+    }
+
+    fn check_quantity(env: &Env, quantity: i128) {
+        if quantity <= 0 {
+            panic_with_error!(env, GoodsError::NegativeAmount);
+        }
+    }
+
+    /// Set the stock of goods, it can't be less than the goods sold as tokens or reserved
+    pub fn set_amount(env: &Env, config: &mut Config, amount: u64) {
+        if (amount as i128) < GoodsToken::supply(env) + Self::reserved(env) {
+            panic_with_error!(env, GoodsError::AmountExceeded);
+        }
+        config.amount = amount;
+        config.save(env);
+    }
+
+    /// Add goods to the stock
+    pub fn restock(env: &Env, config: &mut Config, quantity: u64) {
+        config.amount = config
+            .amount
+            .checked_add(quantity)
+            .unwrap_or_else(|| panic_with_error!(env, Error::BadArgs));
+        config.save(env);
+        env.events().publish((symbol_short!("restock"),), quantity);
+    }
+
+    /// Hold the 'quantity' of available goods for the 'buyer' until 'expires_at' ledger timestamp,
+    /// the quantity is added to the existing reservation of the buyer
+    pub fn reserve(env: &Env, config: &Config, buyer: &Address, quantity: i128, expires_at: u64) {
+        Self::check_quantity(env, quantity);
+        if expires_at <= env.ledger().timestamp() {
+            panic_with_error!(env, Error::BadArgs);
+        }
+        if Self::available(env, config) < quantity {
+            panic_with_error!(env, GoodsError::AmountExceeded);
+        }
+        let key = ReservationKey(buyer.clone());
+        let reserved_before = Self::reservation(env, buyer)
+            .map(|reservation| reservation.quantity)
+            .unwrap_or(0);
+        let reservation = Reservation {
+            quantity: reserved_before + quantity,
+            expires_at,
+        };
+        env.storage().persistent().set(&key, &reservation);
+        Ttl::extend(env, &key);
+        Self::set_reserved(env, Self::reserved(env) + quantity);
+        env.events()
+            .publish((symbol_short!("reserve"), buyer.clone()), (quantity, expires_at));
+    }
+
+    /// Return the reserved goods of the 'buyer' to the available stock,
+    /// the admin can release any reservation, anyone can release the expired one
+    pub fn release_reservation(env: &Env, buyer: &Address) {
+        let reservation = Self::reservation(env, buyer)
+            .unwrap_or_else(|| panic_with_error!(env, GoodsError::ReservationNotFound));
+        if env.ledger().timestamp() <= reservation.expires_at {
+            Config::require_admin(env);
+        }
+        Self::remove_reservation(env, buyer, reservation.quantity);
+    }
+
+    fn remove_reservation(env: &Env, buyer: &Address, quantity: i128) {
+        env.storage().persistent().remove(&ReservationKey(buyer.clone()));
+        Self::set_reserved(env, Self::reserved(env) - quantity);
+        env.events()
+            .publish((symbol_short!("release"), buyer.clone()), quantity);
+    }
+
+    /// Take the 'quantity' of goods for minting to the holder,
+    /// the active reservation of the holder is used first, then the available stock
+    pub fn allocate(env: &Env, config: &Config, holder: &Address, quantity: i128) {
+        let key = ReservationKey(holder.clone());
+        if let Some(mut reservation) = Self::reservation(env, holder) {
+            if env.ledger().timestamp() > reservation.expires_at {
+                Self::remove_reservation(env, holder, reservation.quantity);
+            } else {
+                let used = reservation.quantity.min(quantity);
+                reservation.quantity -= used;
+                if reservation.quantity == 0 {
+                    env.storage().persistent().remove(&key);
+                } else {
+                    env.storage().persistent().set(&key, &reservation);
+                }
+                // the used part of the reservation becomes available for this mint
+                Self::set_reserved(env, Self::reserved(env) - used);
+            }
+        }
+        if Self::available(env, config) < quantity {
+            panic_with_error!(env, GoodsError::AmountExceeded);
+        }
+    }
+
+    /// Deliver the 'quantity' of goods to the holder, its goods tokens are burned
+    /// and the goods leave the stock
+    pub fn fulfil(env: &Env, config: &mut Config, holder: &Address, quantity: i128) {
+        Self::check_quantity(env, quantity);
+        GoodsToken::redeem(env, holder, quantity);
+        config.amount -= quantity as u64;
+        config.save(env);
+        env.storage().instance().set(&SOLD, &(Self::sold(env) + quantity));
+        env.events()
+            .publish((symbol_short!("fulfil"), holder.clone()), quantity);
+    }
+}
\ No newline at end of file
@@ -5,3 +5,11 @@ mod store;
 mod error;
 mod upgrade;
 mod auth;
+mod config;
+mod schema;
+mod token;
+mod inventory;
+mod pricing;
+
+pub use crate::contract::GoodsContract;
+pub use crate::error::GoodsError;
@@ -0,0 +1,127 @@
+use crate::error::GoodsError;
+use crate::store::PRICES;
+use contract_types::goods::{Price, PriceChange, PriceTier};
+use marketplace_asset::ttl::Ttl;
+use marketplace_asset::Error;
+use soroban_sdk::{panic_with_error, symbol_short, vec, Bytes, Env, Vec};
+
+/// XDR of the stellar native asset
+const NATIVE_ASSET_XDR: [u8; 4] = [0; 4];
+
+pub struct Pricing;
+
+impl Pricing {
+    /// Return all price changes sorted by the effective timestamp, including scheduled ones
+    pub fn history(env: &Env) -> Vec<PriceChange> {
+        Ttl::extend(env, &PRICES);
+        env.storage().persistent().get(&PRICES).unwrap_or(vec![env])
+    }
+
+    /// Return the price change effective at the 'timestamp'
+    pub fn at(env: &Env, timestamp: u64) -> Result<PriceChange, GoodsError> {
+        let mut effective = None;
+        for change in Self::history(env).iter() {
+            if change.effective_from > timestamp {
+                break;
+            }
+            effective = Some(change);
+        }
+        effective.ok_or(GoodsError::PriceNotFound)
+    }
+
+    /// Return the total price of the 'quantity' of goods at the 'timestamp',
+    /// the tier with the greatest 'min_quantity' not above the quantity is applied
+    pub fn quote(env: &Env, quantity: i128, timestamp: u64) -> Result<Price, GoodsError> {
+        if quantity <= 0 {
+            return Err(GoodsError::NegativeAmount);
+        }
+        let change = Self::at(env, timestamp)?;
+        let mut unit_amount = change.price.amount;
+        for tier in change.tiers.iter() {
+            if tier.min_quantity > quantity {
+                break;
+            }
+            unit_amount = tier.amount;
+        }
+        let amount = unit_amount.checked_mul(quantity).ok_or(GoodsError::AmountExceeded)?;
+        Ok(Price { amount, ..change.price })
+    }
+
+    fn validate(env: &Env, price: &Price, tiers: &Vec<PriceTier>) {
+        if price.amount < 0 {
+            panic_with_error!(env, GoodsError::NegativeAmount);
+        }
+        let mut min_quantity = 1;
+        for tier in tiers.iter() {
+            if tier.amount < 0 {
+                panic_with_error!(env, GoodsError::NegativeAmount);
+            }
+            if tier.min_quantity <= min_quantity {
+                panic_with_error!(env, Error::BadArgs);
+            }
+            min_quantity = tier.min_quantity;
+        }
+    }
+
+    /// Schedule the price change at the 'effective_from' ledger timestamp,
+    /// the change is applied now if the timestamp is in the past.
+    /// A scheduled change at the same timestamp is replaced, the past changes are never altered
+    pub fn schedule(env: &Env, price: Price, tiers: Vec<PriceTier>, effective_from: u64) {
+        Self::validate(env, &price, &tiers);
+        let effective_from = effective_from.max(env.ledger().timestamp());
+        let change = PriceChange { price, tiers, effective_from };
+
+        let mut history = Self::history(env);
+        let mut index = history.len();
+        for (i, existing) in history.iter().enumerate() {
+            if existing.effective_from >= effective_from {
+                index = i as u32;
+                break;
+            }
+        }
+        if let Some(existing) = history.get(index) {
+            if existing.effective_from == effective_from {
+                history.remove(index);
+            }
+        }
+        history.insert(index, change.clone());
+        env.storage().persistent().set(&PRICES, &history);
+        Ttl::extend(env, &PRICES);
+        env.events()
+            .publish((symbol_short!("price"), symbol_short!("schedule")), change);
+    }
+
+    /// Put the price of the schema version 2 first in the history, so buyers can verify
+    /// past purchases. The price had no currency, it is kept in whole units of the native asset
+    pub fn migrate_legacy(env: &Env, amount: u64) {
+        let native = env
+            .deployer()
+            .with_stellar_asset(Bytes::from_array(env, &NATIVE_ASSET_XDR))
+            .deployed_address();
+        let change = PriceChange {
+            price: Price { amount: amount as i128, asset: native, decimals: 0 },
+            tiers: vec![env],
+            effective_from: 0,
+        };
+        let mut history = Self::history(env);
+        history.push_front(change);
+        env.storage().persistent().set(&PRICES, &history);
+        Ttl::extend(env, &PRICES);
+    }
+
+    /// Cancel the price change scheduled at the 'effective_from' ledger timestamp
+    pub fn cancel(env: &Env, effective_from: u64) {
+        if effective_from <= env.ledger().timestamp() {
+            panic_with_error!(env, Error::BadArgs);
+        }
+        let mut history = Self::history(env);
+        let index = history
+            .iter()
+            .position(|change| change.effective_from == effective_from)
+            .unwrap_or_else(|| panic_with_error!(env, GoodsError::PriceNotFound));
+        history.remove(index as u32);
+        env.storage().persistent().set(&PRICES, &history);
+        env.events()
+            .publish((symbol_short!("price"), symbol_short!("cancel")), effective_from);
+    }
+}
\ No newline at end of file
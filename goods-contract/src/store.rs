@@ -1,12 +1,34 @@
-use soroban_sdk::{contracttype, symbol_short, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Symbol};
 
-#[contracttype]
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum MediaType {
-    ImageBase64 = 1,
-    ImageLink = 2,
-    VideoLink = 4,
-}
+// Storage placement:
+// - instance storage keeps the smart contract instance, its code and the goods description alive
+// - persistent storage keeps the media, price history, balances, authorization of holders
+//   and reservations,
+//   entries are extended when they are changed and can be extended explicitly
+// - temporary storage keeps allowances until their expiration ledger
+// Media and upgrade keys are defined by marketplace_asset, keys of holders by contract_types
+
+/// Config is the goods description in the instance storage. Value is a Config
+pub(crate) const CONFIG: Symbol = symbol_short!("Config");
+
+/// Schema is a version of the storage schema. Value is an u32
+pub(crate) const SCHEMA: Symbol = symbol_short!("Schema");
+
+/// Supply is an amount of goods tokens minted and not burned yet. Value is an i128
+pub(crate) const SUPPLY: Symbol = symbol_short!("Supply");
+
+/// Reserved is an amount of goods held by reservations. Value is an i128
+pub(crate) const RESERVED: Symbol = symbol_short!("Reserved");
+
+/// Sold is an amount of goods delivered to holders of goods tokens. Value is an i128
+pub(crate) const SOLD: Symbol = symbol_short!("Sold");
+
+/// Prices is a history of price changes sorted by the effective timestamp,
+/// scheduled changes are kept at the end. Value is an array of PriceChange
+pub(crate) const PRICES: Symbol = symbol_short!("Prices");
+
+// The goods description was kept in separate persistent entries before it was moved
+// into the instance storage, these keys are read only to migrate deployed smart contracts
 
 /// ID is a unique id of the goods. Value is a String
 pub(crate) const ID: Symbol = symbol_short!("id");
@@ -20,31 +42,18 @@ pub(crate) const NAME: Symbol = symbol_short!("Name");
 /// Description is a description of a good. Value is a String
 pub(crate) const DESCRIPTION: Symbol = symbol_short!("Desc");
 
-/// Price is a current price of a good. Value is a f64
+/// Price is a price of a good without currency. Value is an u64
 pub(crate) const PRICE: Symbol = symbol_short!("Price");
 
 /// Amount is a current available amount of goods. Value is an i64
 pub(crate) const AMOUNT: Symbol = symbol_short!("Amount");
 
-/// MediaList is a list of media_id added to the smart contract. Value is an array of string
-pub(crate) const MEDIA_LIST: Symbol = symbol_short!("MediaList");
-
 /// Toml is representation of toml file link. Value is String.
 pub(crate) const TOML: Symbol = symbol_short!("toml");
 
 #[contracttype]
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct MediaInfo {
-    pub(crate) media_id: String,
-    pub(crate) media_type: MediaType,
-    pub(crate) total_blocks: u64,
-    pub(crate) media: String,
-}
-
-#[contracttype]
-pub enum StorageKey {
-    /// Media is representation of a media data. Value is MediaInfo.
-    Media(String),
-    /// MediaBlock is representation of a media data block as a base64 string. Value is String.
-    MediaBlock(String, u64),
+pub struct AllowanceValue {
+    pub(crate) amount: i128,
+    pub(crate) expiration_ledger: u32,
 }
@@ -0,0 +1,207 @@
+use crate::config::Config;
+use crate::error::GoodsError;
+use crate::inventory::Inventory;
+use crate::store::{AllowanceValue, SUPPLY};
+use contract_types::goods::StorageKey::{Allowance, Balance, Deauthorized};
+use marketplace_asset::ttl::Ttl;
+use marketplace_asset::Error;
+use soroban_sdk::{panic_with_error, symbol_short, Address, Env, String};
+
+/// Goods tokens are whole units of goods
+const DECIMALS: u32 = 0;
+
+/// GoodsToken keeps balances of goods units, holders can transfer them like any stellar asset,
+/// the admin mints units within the available amount of goods
+pub struct GoodsToken;
+
+impl GoodsToken {
+    fn check_amount(env: &Env, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, GoodsError::NegativeAmount);
+        }
+    }
+
+    fn check_authorized(env: &Env, id: &Address) {
+        if !Self::authorized(env, id) {
+            panic_with_error!(env, GoodsError::NotAuthorized);
+        }
+    }
+
+    pub fn balance(env: &Env, id: &Address) -> i128 {
+        let key = Balance(id.clone());
+        Ttl::extend(env, &key);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    fn receive_balance(env: &Env, id: &Address, amount: i128) {
+        let balance = Self::balance(env, id);
+        let key = Balance(id.clone());
+        env.storage().persistent().set(&key, &(balance + amount));
+        Ttl::extend(env, &key);
+    }
+
+    fn spend_balance(env: &Env, id: &Address, amount: i128) {
+        let balance = Self::balance(env, id);
+        if balance < amount {
+            panic_with_error!(env, GoodsError::InsufficientBalance);
+        }
+        env.storage().persistent().set(&Balance(id.clone()), &(balance - amount));
+    }
+
+    pub fn supply(env: &Env) -> i128 {
+        env.storage().instance().get(&SUPPLY).unwrap_or(0)
+    }
+
+    fn set_supply(env: &Env, supply: i128) {
+        env.storage().instance().set(&SUPPLY, &supply);
+    }
+
+    pub fn allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+        let key = Allowance(from.clone(), spender.clone());
+        match env.storage().temporary().get::<_, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => {
+                allowance.amount
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn approve(env: &Env, from: &Address, spender: &Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        Self::check_amount(env, amount);
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(env, Error::BadArgs);
+        }
+        let key = Allowance(from.clone(), spender.clone());
+        let allowance = AllowanceValue { amount, expiration_ledger };
+        env.storage().temporary().set(&key, &allowance);
+        if amount > 0 {
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        }
+        env.events().publish(
+            (symbol_short!("approve"), from.clone(), spender.clone()),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let key = Allowance(from.clone(), spender.clone());
+        let allowance = Self::allowance(env, from, spender);
+        if allowance < amount {
+            panic_with_error!(env, GoodsError::InsufficientAllowance);
+        }
+        if amount > 0 {
+            let mut value: AllowanceValue = env.storage().temporary().get(&key).unwrap();
+            value.amount -= amount;
+            env.storage().temporary().set(&key, &value);
+        }
+    }
+
+    fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+        Self::check_amount(env, amount);
+        Self::check_authorized(env, from);
+        Self::check_authorized(env, to);
+        Self::spend_balance(env, from, amount);
+        Self::receive_balance(env, to, amount);
+        env.events()
+            .publish((symbol_short!("transfer"), from.clone(), to.clone()), amount);
+    }
+
+    pub fn transfer(env: &Env, from: &Address, to: &Address, amount: i128) {
+        from.require_auth();
+        Self::move_balance(env, from, to, amount);
+    }
+
+    pub fn transfer_from(env: &Env, spender: &Address, from: &Address, to: &Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(env, from, spender, amount);
+        Self::move_balance(env, from, to, amount);
+    }
+
+    fn burn_balance(env: &Env, from: &Address, amount: i128) {
+        Self::check_amount(env, amount);
+        Self::check_authorized(env, from);
+        Self::spend_balance(env, from, amount);
+        Self::set_supply(env, Self::supply(env) - amount);
+        env.events().publish((symbol_short!("burn"), from.clone()), amount);
+    }
+
+    pub fn burn(env: &Env, from: &Address, amount: i128) {
+        from.require_auth();
+        Self::burn_balance(env, from, amount);
+    }
+
+    pub fn burn_from(env: &Env, spender: &Address, from: &Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(env, from, spender, amount);
+        Self::burn_balance(env, from, amount);
+    }
+
+    pub fn decimals() -> u32 {
+        DECIMALS
+    }
+
+    pub fn name(config: &Config) -> String {
+        config.name.clone()
+    }
+
+    pub fn symbol(config: &Config) -> String {
+        config.id.clone()
+    }
+
+    pub fn authorized(env: &Env, id: &Address) -> bool {
+        !env.storage()
+            .persistent()
+            .get::<_, bool>(&Deauthorized(id.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Authorized holders can transfer and burn goods tokens, all holders are authorized by default,
+    /// a holder with an active reservation can't be deauthorized until the reservation is released
+    pub fn set_authorized(env: &Env, config: &Config, id: &Address, authorize: bool) {
+        let key = Deauthorized(id.clone());
+        if authorize {
+            env.storage().persistent().remove(&key);
+        } else {
+            if Inventory::active_reservation(env, id).is_some() {
+                panic_with_error!(env, GoodsError::ReservationActive);
+            }
+            env.storage().persistent().set(&key, &true);
+            Ttl::extend(env, &key);
+        }
+        env.events().publish(
+            (symbol_short!("set_auth"), config.admin.clone(), id.clone()),
+            authorize,
+        );
+    }
+
+    /// Mint goods tokens, the reservation of the holder is used first,
+    /// then the available amount of goods
+    pub fn mint(env: &Env, config: &Config, to: &Address, amount: i128) {
+        Self::check_amount(env, amount);
+        Self::check_authorized(env, to);
+        Inventory::allocate(env, config, to, amount);
+        Self::set_supply(env, Self::supply(env) + amount);
+        Self::receive_balance(env, to, amount);
+        env.events()
+            .publish((symbol_short!("mint"), config.admin.clone(), to.clone()), amount);
+    }
+
+    /// Burn goods tokens of the holder the goods are delivered to
+    pub fn redeem(env: &Env, from: &Address, amount: i128) {
+        Self::check_amount(env, amount);
+        Self::spend_balance(env, from, amount);
+        Self::set_supply(env, Self::supply(env) - amount);
+        env.events().publish((symbol_short!("burn"), from.clone()), amount);
+    }
+
+    /// Claw back goods tokens, the clawed back units are returned to the available amount
+    pub fn clawback(env: &Env, config: &Config, from: &Address, amount: i128) {
+        Self::check_amount(env, amount);
+        Self::spend_balance(env, from, amount);
+        Self::set_supply(env, Self::supply(env) - amount);
+        env.events()
+            .publish((symbol_short!("clawback"), config.admin.clone(), from.clone()), amount);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+
+//! Deploys the master, goods and demand contracts together into a test environment,
+//! integration tests of the marketplace go to the tests directory of this crate
+
+use contract_types::goods::{GoodsClient, Price};
+use demand_contract::{DemandContract, DemandContractClient};
+use goods_contract::GoodsContract;
+use master_contract::{PaymentContract, PaymentContractClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::xdr::{
+    AccountEntry, AccountEntryExt, AccountFlags, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+    LedgerKeyAccount, ScAddress, SequenceNumber, Thresholds,
+};
+use soroban_sdk::{Address, Env, String};
+use std::rc::Rc;
+
+/// Stellar account of the admin, it issues the pay asset and the order assets
+pub const ADMIN_ACCOUNT: &str = "GCZMWP4RXU2QQV4ASC3PF6K4URGMBES4UR5GS2QYDUACL5SDSQWTDX52";
+
+pub struct Marketplace<'a> {
+    /// admin of the master contract and issuer of the pay asset
+    pub admin: Address,
+    /// admin of the goods contract
+    pub seller: Address,
+    /// admin of the demand contract
+    pub buyer: Address,
+    /// asset the master contract pays out and the goods are priced in
+    pub pay_asset: TokenClient<'a>,
+    pub master: PaymentContractClient<'a>,
+    pub goods: GoodsClient<'a>,
+    pub demand: DemandContractClient<'a>,
+}
+
+impl<'a> Marketplace<'a> {
+    /// Deploy all smart contracts, the goods are managed by the seller and the demand
+    /// by the buyer, authorization is mocked
+    pub fn deploy(env: &Env) -> Marketplace<'a> {
+        env.mock_all_auths();
+        let admin = Address::from_str(env, ADMIN_ACCOUNT);
+        create_account(env, &admin);
+        let seller = Address::generate(env);
+        let buyer = Address::generate(env);
+        let pay_asset = env.register_stellar_asset_contract_v2(admin.clone());
+        let pay_asset = TokenClient::new(env, &pay_asset.address());
+
+        let master = env.register(
+            PaymentContract,
+            (admin.clone(), pay_asset.symbol(), Some(pay_asset.address.clone())),
+        );
+        let price = Price {
+            amount: 100,
+            asset: pay_asset.address.clone(),
+            decimals: 2,
+        };
+        let goods = env.register(
+            GoodsContract,
+            (
+                seller.clone(),
+                String::from_str(env, "goods"),
+                String::from_str(env, "Goods"),
+                String::from_str(env, "Goods for the integration tests"),
+                price,
+                10_u64,
+                String::from_str(env, ""),
+            ),
+        );
+        let demand = env.register(
+            DemandContract,
+            (
+                buyer.clone(),
+                String::from_str(env, "demand"),
+                String::from_str(env, "Demand"),
+                String::from_str(env, "Demand for the integration tests"),
+                1000_u64,
+                String::from_str(env, ""),
+            ),
+        );
+        Marketplace {
+            admin,
+            seller,
+            buyer,
+            pay_asset,
+            master: PaymentContractClient::new(env, &master),
+            goods: GoodsClient::new(env, &goods),
+            demand: DemandContractClient::new(env, &demand),
+        }
+    }
+}
+
+impl Marketplace<'_> {
+    /// Register a new payer address under the 'id' in the master contract
+    pub fn add_payer(&self, id: &str) -> Address {
+        let env = &self.master.env;
+        let payer = Address::generate(env);
+        self.master.add_payer(&String::from_str(env, id), &payer);
+        payer
+    }
+
+    /// Mint the default pay asset to the master contract treasury for payouts
+    pub fn fund_treasury(&self, amount: i128) {
+        StellarAssetClient::new(&self.master.env, &self.pay_asset.address)
+            .mint(&self.master.address, &amount);
+    }
+}
+
+/// Create the ledger entry of the stellar 'account', assets issued by the account
+/// can be minted only if the issuer exists. The account is revocable and allows clawback,
+/// the master contract claws back the order and pay assets it issues
+pub fn create_account(env: &Env, account: &Address) {
+    let ScAddress::Account(account_id) = ScAddress::from(account) else {
+        panic!("not a stellar account");
+    };
+    let key = LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id.clone(),
+    });
+    let entry = LedgerEntry {
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id,
+            balance: 0,
+            flags: (AccountFlags::RevocableFlag as u32) | (AccountFlags::ClawbackEnabledFlag as u32),
+            home_domain: Default::default(),
+            inflation_dest: None,
+            num_sub_entries: 0,
+            seq_num: SequenceNumber(0),
+            thresholds: Thresholds([1; 4]),
+            signers: Default::default(),
+            ext: AccountEntryExt::V0,
+        }),
+        last_modified_ledger_seq: 0,
+        ext: LedgerEntryExt::V0,
+    };
+    env.host()
+        .add_ledger_entry(&Rc::new(key), &Rc::new(entry), None)
+        .unwrap();
+}
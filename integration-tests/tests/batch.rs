@@ -0,0 +1,104 @@
+use integration_tests::Marketplace;
+use master_contract::{MintRequest, TransferRequest};
+use soroban_sdk::{vec, Env, String, Vec};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn id(env: &Env, prefix: &str, index: u32) -> String {
+    String::from_str(env, &format!("{prefix}{index}"))
+}
+
+/// CPU instructions and memory bytes of the last invocation
+fn cost(env: &Env) -> (u64, u64) {
+    let budget = env.cost_estimate().budget();
+    (budget.cpu_instruction_cost(), budget.memory_bytes_cost())
+}
+
+/// Run the batches of 'size' items for one order and return the cost of mint_batch,
+/// transfer_batch and approve_transfers
+fn batch_costs(size: u32) -> [(u64, u64); 3] {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let market = Marketplace::deploy(&env);
+    market.add_payer("alice");
+    market.add_payer("bob");
+    market.fund_treasury(i128::from(size) * 100);
+    let order = str(&env, "order");
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+
+    let mut mints = Vec::new(&env);
+    let mut transfers = Vec::new(&env);
+    let mut ids = Vec::new(&env);
+    for i in 0..size {
+        mints.push_back(MintRequest {
+            order: order.clone(),
+            payment: id(&env, "p", i),
+            payer: str(&env, "alice"),
+            amount: 100,
+            fee: 0,
+            settlement: None,
+        });
+        transfers.push_back(TransferRequest {
+            order: order.clone(),
+            transfer: id(&env, "t", i),
+            payer: str(&env, "alice"),
+            beneficiary: str(&env, "bob"),
+            amount: 100,
+            fee: 0,
+        });
+        ids.push_back(id(&env, "t", i));
+    }
+    assert_eq!(market.master.mint_batch(&mints).len(), size);
+    let mint = cost(&env);
+    assert_eq!(market.master.transfer_batch(&transfers).len(), size);
+    let transfer = cost(&env);
+    assert_eq!(market.master.approve_transfers(&order, &ids).len(), size);
+    let approve = cost(&env);
+    [mint, transfer, approve]
+}
+
+#[test]
+fn batch_cost_within_transaction_limits() {
+    let single = batch_costs(1);
+    for size in [5, 10, 20] {
+        let batch = batch_costs(size);
+        for (item, (cpu, memory)) in batch.iter().enumerate() {
+            // limits of a transaction on the network
+            assert!(*cpu < 100_000_000);
+            assert!(*memory < 41_943_040);
+            // an item of the batch costs less than 1M cpu instructions and 150 KB of memory,
+            // 20 items approved take about 15M cpu instructions and 2.4 MB of memory
+            assert!(*cpu < 1_000_000 * u64::from(size));
+            assert!(*memory < 150_000 * u64::from(size));
+            // the batch is cheaper than the same number of single item calls
+            assert!(*cpu < single[item].0 * u64::from(size));
+        }
+    }
+}
+
+#[test]
+fn mint_batch_creates_orders() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    market.add_payer("alice");
+
+    let mut mints = Vec::new(&env);
+    for i in 0..2 {
+        mints.push_back(MintRequest {
+            order: id(&env, "order", i),
+            payment: id(&env, "p", i),
+            payer: str(&env, "alice"),
+            amount: 100,
+            fee: 10,
+            settlement: None,
+        });
+    }
+    assert_eq!(market.master.mint_batch(&mints), vec![&env, 90, 90]);
+    for i in 0..2 {
+        assert_eq!(market.master.order_summary(&id(&env, "order", i)).paid, 100);
+    }
+}
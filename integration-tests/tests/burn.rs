@@ -0,0 +1,73 @@
+use integration_tests::Marketplace;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Env, String};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn signed_by(env: &Env, address: &Address) -> bool {
+    env.auths().iter().any(|(signer, _)| signer == address)
+}
+
+#[test]
+fn burn_issued_pay_asset_with_admin_signature() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let payer = Address::generate(&env);
+    market.master.add_payer(&str(&env, "alice"), &payer);
+    let euro = market.master.add_pay_asset(&str(&env, "EUR"), &None);
+    StellarAssetClient::new(&env, &euro).mint(&payer, &100);
+
+    market
+        .master
+        .burn(&str(&env, "alice"), &str(&env, "payout"), &60, &1, &Some(euro.clone()));
+    assert!(signed_by(&env, &market.admin));
+    assert!(!signed_by(&env, &payer));
+    let euro = TokenClient::new(&env, &euro);
+    assert_eq!(euro.balance(&payer), 40);
+    assert_eq!(euro.balance(&market.master.address), 0);
+
+    market.master.reject_burn(&str(&env, "payout"));
+    assert_eq!(euro.balance(&payer), 100);
+}
+
+#[test]
+fn burn_bound_pay_asset_with_payer_signature() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let payer = Address::generate(&env);
+    market.master.add_payer(&str(&env, "alice"), &payer);
+    StellarAssetClient::new(&env, &market.pay_asset.address).mint(&payer, &100);
+
+    // the transfer to the treasury is signed by the payer below the root invocation
+    env.mock_all_auths_allowing_non_root_auth();
+    market
+        .master
+        .burn(&str(&env, "alice"), &str(&env, "payout"), &60, &1, &None);
+    assert!(signed_by(&env, &market.admin));
+    assert!(signed_by(&env, &payer));
+    assert_eq!(market.pay_asset.balance(&payer), 40);
+    assert_eq!(market.master.treasury_balance(&None), 60);
+
+    market.master.reject_burn(&str(&env, "payout"));
+    assert_eq!(market.pay_asset.balance(&payer), 100);
+    assert_eq!(market.master.treasury_balance(&None), 0);
+}
+
+#[test]
+fn burn_bound_pay_asset_requires_payer_signature() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let payer = Address::generate(&env);
+    market.master.add_payer(&str(&env, "alice"), &payer);
+    StellarAssetClient::new(&env, &market.pay_asset.address).mint(&payer, &100);
+
+    // only the root invocation signed by the admin is authorized
+    assert!(market
+        .master
+        .try_burn(&str(&env, "alice"), &str(&env, "payout"), &60, &1, &None)
+        .is_err());
+    assert_eq!(market.pay_asset.balance(&payer), 100);
+}
@@ -0,0 +1,125 @@
+use integration_tests::Marketplace;
+use master_contract::{Error, StorageKey};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::StellarAssetClient;
+use soroban_sdk::{Address, Env, String, Val, Vec};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+#[test]
+fn order_not_found() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    assert_eq!(
+        market.master.try_order_summary(&str(&env, "missing")),
+        Err(Ok(Error::OrderNotFound))
+    );
+    assert_eq!(
+        market.master.try_cancel_order(&str(&env, "missing")),
+        Err(Ok(Error::OrderNotFound))
+    );
+}
+
+#[test]
+fn asset_not_found() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let order = str(&env, "order");
+    let (_, code, issuer) =
+        market
+            .master
+            .deploy(&order, &market.admin, &None, &None, &None, &None);
+    env.as_contract(&market.master.address, || {
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::Asset(code, issuer));
+    });
+    assert_eq!(
+        market.master.try_transfer(
+            &order,
+            &str(&env, "transfer"),
+            &str(&env, "payer"),
+            &str(&env, "beneficiary"),
+            &10,
+            &1,
+        ),
+        Err(Ok(Error::AssetNotFound))
+    );
+}
+
+#[test]
+fn payer_not_found() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    assert_eq!(
+        market.master.try_payer(&str(&env, "nobody")),
+        Err(Ok(Error::PayerNotFound))
+    );
+    assert_eq!(
+        market.master.try_burn(&str(&env, "nobody"), &str(&env, "payout"), &10, &1, &None),
+        Err(Ok(Error::PayerNotFound))
+    );
+}
+
+#[test]
+fn withdraw_not_found() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    assert_eq!(
+        market.master.try_approve_burn(&str(&env, "missing")),
+        Err(Ok(Error::WithdrawNotFound))
+    );
+    assert_eq!(
+        market.master.try_reject_burn(&str(&env, "missing")),
+        Err(Ok(Error::WithdrawNotFound))
+    );
+}
+
+#[test]
+fn pay_asset_missing() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let payer = Address::generate(&env);
+    market.master.add_payer(&str(&env, "alice"), &payer);
+    let euro = market.master.add_pay_asset(&str(&env, "EUR"), &None);
+    StellarAssetClient::new(&env, &euro).mint(&payer, &100);
+
+    let payout = str(&env, "payout");
+    market
+        .master
+        .burn(&str(&env, "alice"), &payout, &100, &1, &Some(euro.clone()));
+    market.master.remove_pay_asset(&euro);
+    assert_eq!(
+        market.master.try_approve_burn(&payout),
+        Err(Ok(Error::PayAssetMissing))
+    );
+}
+
+#[test]
+fn withdraw_keeps_list_format() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let payer = Address::generate(&env);
+    market.master.add_payer(&str(&env, "alice"), &payer);
+    StellarAssetClient::new(&env, &market.pay_asset.address).mint(&payer, &100);
+
+    // the payer signs the transfer of the withdrawal to the treasury
+    env.mock_all_auths_allowing_non_root_auth();
+    let payout = str(&env, "payout");
+    market.master.burn(&str(&env, "alice"), &payout, &100, &1, &None);
+    let records: Vec<Val> = env.as_contract(&market.master.address, || {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Withdraw(payout.clone()))
+            .unwrap()
+    });
+    assert_eq!(records.len(), 1);
+
+    market.master.approve_burn(&payout);
+    assert_eq!(
+        market.master.try_approve_burn(&payout),
+        Err(Ok(Error::WithdrawNotFound))
+    );
+}
@@ -0,0 +1,209 @@
+use contract_types::goods::Price;
+use demand_contract::{DemandError, Escrow};
+use integration_tests::Marketplace;
+use marketplace_asset::Error;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::StellarAssetClient;
+use soroban_sdk::xdr::{
+    InvokeContractArgs, ScAddress, ScVal, ScVec, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, VecM,
+};
+use soroban_sdk::{vec, Address, Env};
+
+fn funded(env: &Env, amount: i128) -> Marketplace<'_> {
+    let market = Marketplace::deploy(env);
+    StellarAssetClient::new(env, &market.pay_asset.address).mint(&market.buyer, &1000);
+    market.demand.set_pay_asset(&market.pay_asset.address);
+    market.demand.fund(&amount);
+    market
+}
+
+fn escrow(market: &Marketplace) -> Escrow {
+    market.demand.escrow().unwrap()
+}
+
+#[test]
+fn fund_up_to_budget() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+
+    assert_eq!(market.pay_asset.balance(&market.demand.address), 600);
+    assert_eq!(market.pay_asset.balance(&market.buyer), 400);
+    assert_eq!(escrow(&market).balance, 600);
+
+    assert_eq!(
+        market.demand.try_fund(&401),
+        Err(Ok(DemandError::BudgetExceeded.into()))
+    );
+    assert_eq!(
+        market.demand.try_set_budget(&599),
+        Err(Ok(DemandError::BudgetExceeded.into()))
+    );
+    market.demand.fund(&400);
+    assert_eq!(escrow(&market).balance, 1000);
+}
+
+#[test]
+fn fund_requires_pay_asset() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    assert_eq!(
+        market.demand.try_fund(&100),
+        Err(Ok(DemandError::PayAssetNotSet.into()))
+    );
+}
+
+#[test]
+fn withdraw_unused_budget() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+
+    market.demand.withdraw(&200);
+    assert_eq!(market.pay_asset.balance(&market.buyer), 600);
+    assert_eq!(escrow(&market).balance, 400);
+
+    // 3 goods for 100 each are held for the seller
+    market.demand.select_goods(&market.goods.address, &3);
+    assert_eq!(
+        market.demand.try_withdraw(&101),
+        Err(Ok(DemandError::InsufficientEscrow.into()))
+    );
+    market.demand.withdraw(&100);
+    assert_eq!(escrow(&market).balance, 300);
+}
+
+#[test]
+fn release_to_seller() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+
+    market.demand.select_goods(&market.goods.address, &3);
+    let selection = market.demand.selection().unwrap();
+    assert_eq!(selection.seller, market.seller);
+    assert_eq!(selection.amount, 300);
+
+    market.demand.release();
+    assert_eq!(market.pay_asset.balance(&market.seller), 300);
+    assert_eq!(
+        escrow(&market),
+        Escrow {
+            asset: market.pay_asset.address.clone(),
+            balance: 300,
+            released: 300,
+        }
+    );
+    assert_eq!(market.demand.selection(), None);
+    assert_eq!(
+        market.demand.try_release(),
+        Err(Ok(DemandError::GoodsNotSelected.into()))
+    );
+    // the released amount stays committed to the budget
+    assert_eq!(
+        market.demand.try_fund(&401),
+        Err(Ok(DemandError::BudgetExceeded.into()))
+    );
+}
+
+#[test]
+fn cancel_selection_requires_seller() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+    market.demand.select_goods(&market.goods.address, &3);
+
+    market.demand.cancel_selection();
+    assert!(env
+        .auths()
+        .iter()
+        .any(|(address, _)| *address == market.seller));
+    assert_eq!(market.demand.selection(), None);
+    market.demand.withdraw(&600);
+    assert_eq!(market.pay_asset.balance(&market.buyer), 1000);
+}
+
+#[test]
+fn select_goods_checks_quote() {
+    let env = Env::default();
+    let market = funded(&env, 200);
+
+    assert_eq!(
+        market.demand.try_select_goods(&market.goods.address, &3),
+        Err(Ok(DemandError::InsufficientEscrow.into()))
+    );
+    let free = Price {
+        amount: 0,
+        asset: market.pay_asset.address.clone(),
+        decimals: 2,
+    };
+    market.goods.set_price(&free, &vec![&env]);
+    assert_eq!(
+        market.demand.try_select_goods(&market.goods.address, &3),
+        Err(Ok(Error::BadArgs.into()))
+    );
+    let other = Price {
+        amount: 100,
+        asset: Address::generate(&env),
+        decimals: 2,
+    };
+    market.goods.set_price(&other, &vec![&env]);
+    assert_eq!(
+        market.demand.try_select_goods(&market.goods.address, &1),
+        Err(Ok(DemandError::AssetMismatch.into()))
+    );
+}
+
+#[test]
+fn pay_asset_is_kept_while_funded() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+    market.demand.select_goods(&market.goods.address, &3);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        market.demand.try_set_pay_asset(&other),
+        Err(Ok(DemandError::GoodsSelected.into()))
+    );
+    market.demand.release();
+    assert_eq!(
+        market.demand.try_set_pay_asset(&other),
+        Err(Ok(DemandError::EscrowNotEmpty.into()))
+    );
+}
+
+#[test]
+fn unsigned_transfer_from_demand_fails() {
+    let env = Env::default();
+    let market = funded(&env, 600);
+    let thief = Address::generate(&env);
+
+    let nonce = 1;
+    let entry = SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::from(&market.demand.address),
+            nonce,
+            signature_expiration_ledger: env.ledger().sequence() + 100,
+            signature: ScVal::Vec(Some(ScVec(VecM::default()))),
+        }),
+        root_invocation: SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                contract_address: ScAddress::from(&market.pay_asset.address),
+                function_name: "transfer".try_into().unwrap(),
+                args: std::vec![
+                    ScVal::from(&market.demand.address),
+                    ScVal::from(&thief),
+                    ScVal::from(600_i128),
+                ]
+                .try_into()
+                .unwrap(),
+            }),
+            sub_invocations: VecM::default(),
+        },
+    };
+    env.set_auths(&[entry]);
+    assert!(market
+        .pay_asset
+        .try_transfer(&market.demand.address, &thief, &600)
+        .is_err());
+    assert_eq!(market.pay_asset.balance(&market.demand.address), 600);
+    assert_eq!(market.pay_asset.balance(&thief), 0);
+}
@@ -0,0 +1,175 @@
+use goods_contract::GoodsError;
+use integration_tests::Marketplace;
+use marketplace_asset::Error;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Env, String};
+
+fn token<'a>(market: &Marketplace<'a>) -> TokenClient<'a> {
+    TokenClient::new(&market.goods.env, &market.goods.address)
+}
+
+fn asset<'a>(market: &Marketplace<'a>) -> StellarAssetClient<'a> {
+    StellarAssetClient::new(&market.goods.env, &market.goods.address)
+}
+
+#[test]
+fn mint_bounded_by_amount() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    asset(&market).mint(&market.buyer, &7);
+    assert_eq!(token(&market).balance(&market.buyer), 7);
+    assert_eq!(market.goods.available(), 3);
+    assert_eq!(
+        asset(&market).try_mint(&market.buyer, &4),
+        Err(Ok(GoodsError::AmountExceeded.into()))
+    );
+    asset(&market).mint(&market.buyer, &3);
+    assert_eq!(market.goods.available(), 0);
+    assert_eq!(token(&market).decimals(), 0);
+}
+
+#[test]
+fn transfer_between_authorized_holders() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let other = Address::generate(&env);
+    asset(&market).mint(&market.buyer, &5);
+
+    token(&market).transfer(&market.buyer, &other, &2);
+    assert_eq!(token(&market).balance(&market.buyer), 3);
+    assert_eq!(token(&market).balance(&other), 2);
+    assert_eq!(
+        token(&market).try_transfer(&market.buyer, &other, &4),
+        Err(Ok(GoodsError::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        token(&market).try_transfer(&market.buyer, &other, &-1),
+        Err(Ok(GoodsError::NegativeAmount.into()))
+    );
+
+    asset(&market).set_authorized(&other, &false);
+    assert_eq!(
+        token(&market).try_transfer(&market.buyer, &other, &1),
+        Err(Ok(GoodsError::NotAuthorized.into()))
+    );
+    assert_eq!(
+        token(&market).try_transfer(&other, &market.buyer, &1),
+        Err(Ok(GoodsError::NotAuthorized.into()))
+    );
+}
+
+#[test]
+fn allowance_expires() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let spender = Address::generate(&env);
+    let other = Address::generate(&env);
+    asset(&market).mint(&market.buyer, &5);
+    env.ledger().set_sequence_number(100);
+    let expiration = 110;
+
+    token(&market).approve(&market.buyer, &spender, &3, &expiration);
+    assert_eq!(token(&market).allowance(&market.buyer, &spender), 3);
+    token(&market).transfer_from(&spender, &market.buyer, &other, &2);
+    assert_eq!(token(&market).allowance(&market.buyer, &spender), 1);
+    assert_eq!(token(&market).balance(&other), 2);
+    assert_eq!(
+        token(&market).try_transfer_from(&spender, &market.buyer, &other, &2),
+        Err(Ok(GoodsError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        token(&market).try_approve(&market.buyer, &spender, &3, &99),
+        Err(Ok(Error::BadArgs.into()))
+    );
+
+    env.ledger().set_sequence_number(expiration + 1);
+    assert_eq!(token(&market).allowance(&market.buyer, &spender), 0);
+    assert_eq!(
+        token(&market).try_transfer_from(&spender, &market.buyer, &other, &1),
+        Err(Ok(GoodsError::InsufficientAllowance.into()))
+    );
+}
+
+#[test]
+fn burn_returns_goods_to_available() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let spender = Address::generate(&env);
+    asset(&market).mint(&market.buyer, &5);
+
+    token(&market).burn(&market.buyer, &1);
+    assert_eq!(token(&market).balance(&market.buyer), 4);
+    assert_eq!(market.goods.available(), 6);
+
+    token(&market).approve(&market.buyer, &spender, &2, &(env.ledger().sequence() + 10));
+    token(&market).burn_from(&spender, &market.buyer, &2);
+    assert_eq!(token(&market).balance(&market.buyer), 2);
+    assert_eq!(token(&market).allowance(&market.buyer, &spender), 0);
+    assert_eq!(
+        token(&market).try_burn_from(&spender, &market.buyer, &1),
+        Err(Ok(GoodsError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        token(&market).try_burn(&market.buyer, &3),
+        Err(Ok(GoodsError::InsufficientBalance.into()))
+    );
+
+    asset(&market).set_authorized(&market.buyer, &false);
+    assert_eq!(
+        token(&market).try_burn(&market.buyer, &1),
+        Err(Ok(GoodsError::NotAuthorized.into()))
+    );
+}
+
+#[test]
+fn clawback_by_admin() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    asset(&market).mint(&market.buyer, &5);
+
+    // the deauthorized holder can't move its tokens, the admin claws them back
+    asset(&market).set_authorized(&market.buyer, &false);
+    asset(&market).clawback(&market.buyer, &2);
+    assert_eq!(env.auths()[0].0, market.seller);
+    assert_eq!(token(&market).balance(&market.buyer), 3);
+    assert_eq!(market.goods.available(), 7);
+    assert_eq!(
+        asset(&market).try_clawback(&market.buyer, &4),
+        Err(Ok(GoodsError::InsufficientBalance.into()))
+    );
+}
+
+#[test]
+fn holder_with_active_reservation_stays_authorized() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(1_000);
+    market.goods.reserve(&market.buyer, &3, &2_000);
+
+    assert_eq!(
+        asset(&market).try_set_authorized(&market.buyer, &false),
+        Err(Ok(GoodsError::ReservationActive.into()))
+    );
+    assert!(asset(&market).authorized(&market.buyer));
+
+    // the expired reservation no longer holds the goods for the buyer
+    env.ledger().set_timestamp(2_001);
+    asset(&market).set_authorized(&market.buyer, &false);
+    assert!(!asset(&market).authorized(&market.buyer));
+}
+
+#[test]
+fn metadata_and_current_price() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    let metadata = market.goods.metadata();
+    assert_eq!(metadata.id, String::from_str(&env, "goods"));
+    assert_eq!(metadata.admin, market.seller);
+    assert_eq!(metadata.amount, 10);
+    assert!(metadata.media_list.is_empty());
+    let current = market.goods.price();
+    assert_eq!((current.price.amount, current.effective_from), (100, 0));
+}
@@ -0,0 +1,120 @@
+use goods_contract::GoodsError;
+use integration_tests::Marketplace;
+use marketplace_asset::Error;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Env};
+
+fn mint(market: &Marketplace, to: &Address, amount: i128) {
+    StellarAssetClient::new(&market.goods.env, &market.goods.address).mint(to, &amount);
+}
+
+fn balance(market: &Marketplace, id: &Address) -> i128 {
+    TokenClient::new(&market.goods.env, &market.goods.address).balance(id)
+}
+
+#[test]
+fn restock_adds_available_goods() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    market.goods.restock(&5);
+    assert_eq!(market.goods.amount(), 15);
+    assert_eq!(market.goods.available(), 15);
+    assert_eq!(
+        market.goods.try_restock(&u64::MAX),
+        Err(Ok(Error::BadArgs.into()))
+    );
+}
+
+#[test]
+fn reservation_holds_goods_for_buyer() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let other = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+
+    market.goods.reserve(&market.buyer, &6, &2_000);
+    assert_eq!(market.goods.reserved(), 6);
+    assert_eq!(market.goods.available(), 4);
+    assert_eq!(
+        market.goods.try_reserve(&other, &5, &2_000),
+        Err(Ok(GoodsError::AmountExceeded.into()))
+    );
+    assert_eq!(
+        market.goods.try_reserve(&other, &1, &1_000),
+        Err(Ok(Error::BadArgs.into()))
+    );
+
+    // the reservation is used first, the rest comes from the available goods
+    mint(&market, &market.buyer, 8);
+    assert_eq!(balance(&market, &market.buyer), 8);
+    assert_eq!(market.goods.reservation(&market.buyer), None);
+    assert_eq!(market.goods.reserved(), 0);
+    assert_eq!(market.goods.available(), 2);
+}
+
+#[test]
+fn reserved_goods_are_not_oversold() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let other = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+    market.goods.reserve(&market.buyer, &6, &2_000);
+
+    let minted = StellarAssetClient::new(&env, &market.goods.address).try_mint(&other, &5);
+    assert_eq!(minted, Err(Ok(GoodsError::AmountExceeded.into())));
+    mint(&market, &other, 4);
+    assert_eq!(
+        market.goods.try_set_amount(&9),
+        Err(Ok(GoodsError::AmountExceeded.into()))
+    );
+    market.goods.set_amount(&10);
+}
+
+#[test]
+fn release_reservation() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(1_000);
+    market.goods.reserve(&market.buyer, &3, &2_000);
+    market.goods.reserve(&market.buyer, &2, &2_000);
+    assert_eq!(market.goods.reservation(&market.buyer).unwrap().quantity, 5);
+
+    // only the admin releases the active reservation
+    env.set_auths(&[]);
+    assert!(market.goods.try_release_reservation(&market.buyer).is_err());
+    assert_eq!(market.goods.reserved(), 5);
+
+    // anyone releases the expired one
+    env.ledger().set_timestamp(2_001);
+    market.goods.release_reservation(&market.buyer);
+    assert_eq!(market.goods.reservation(&market.buyer), None);
+    assert_eq!(market.goods.reserved(), 0);
+    assert_eq!(market.goods.available(), 10);
+    assert_eq!(
+        market.goods.try_release_reservation(&market.buyer),
+        Err(Ok(GoodsError::ReservationNotFound.into()))
+    );
+}
+
+#[test]
+fn fulfil_burns_tokens_and_counts_sold_goods() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    mint(&market, &market.buyer, 4);
+
+    market.goods.fulfil(&market.buyer, &3);
+    assert_eq!(balance(&market, &market.buyer), 1);
+    assert_eq!(market.goods.amount(), 7);
+    assert_eq!(market.goods.sold(), 3);
+    assert_eq!(market.goods.available(), 6);
+    assert_eq!(
+        market.goods.try_fulfil(&market.buyer, &2),
+        Err(Ok(GoodsError::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        market.goods.try_fulfil(&market.buyer, &0),
+        Err(Ok(GoodsError::NegativeAmount.into()))
+    );
+}
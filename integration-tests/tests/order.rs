@@ -0,0 +1,261 @@
+use integration_tests::Marketplace;
+use master_contract::{Error, OrderStatus, StorageKey, MAX_REFUNDS};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{Env, String};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn has_state(env: &Env, market: &Marketplace, order: &String) -> bool {
+    env.as_contract(&market.master.address, || {
+        env.storage()
+            .persistent()
+            .has(&StorageKey::OrderState(order.clone()))
+    })
+}
+
+/// Deploy the order with the payment deadline at 100 and the delivery deadline at 200,
+/// the payer "alice" pays 'paid' order asset units
+fn paid_order<'a>(env: &Env, paid: i128) -> (Marketplace<'a>, String) {
+    let market = Marketplace::deploy(env);
+    market.add_payer("alice");
+    market.fund_treasury(10_000);
+    let order = str(env, "order");
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &Some(100), &Some(200));
+    if paid > 0 {
+        market
+            .master
+            .mint(&order, &str(env, "payment"), &str(env, "alice"), &paid, &0, &None);
+    }
+    (market, order)
+}
+
+#[test]
+fn expire_unpaid_order_without_signature() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 0);
+
+    assert_eq!(
+        market.master.try_expire_order(&order),
+        Err(Ok(Error::OrderNotExpired))
+    );
+    env.ledger().set_timestamp(101);
+    env.set_auths(&[]);
+    market.master.expire_order(&order);
+    assert!(env.auths().is_empty());
+    assert_eq!(market.master.order_summary(&order).status, OrderStatus::Expired);
+    assert_eq!(
+        market.master.try_expire_order(&order),
+        Err(Ok(Error::OrderClosed))
+    );
+}
+
+#[test]
+fn funded_order_expires_after_delivery_deadline() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 300);
+    let payer = market.master.payer(&str(&env, "alice"));
+
+    env.ledger().set_timestamp(101);
+    assert_eq!(
+        market.master.try_expire_order(&order),
+        Err(Ok(Error::OrderNotExpired))
+    );
+    env.ledger().set_timestamp(201);
+    market.master.expire_order(&order);
+    assert_eq!(market.pay_asset.balance(&payer), 0);
+
+    // the outstanding balance is refunded by the admin
+    assert_eq!(market.master.refund_order(&order, &10), 0);
+    assert!(env.auths().iter().any(|(signer, _)| *signer == market.admin));
+    assert_eq!(market.pay_asset.balance(&payer), 300);
+    let summary = market.master.order_summary(&order);
+    assert_eq!(summary.status, OrderStatus::Expired);
+    assert_eq!(summary.refunded, 300);
+}
+
+#[test]
+fn expired_order_refunded_by_admin() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 300);
+    let (asset, _, _) = market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+    let order_asset = TokenClient::new(&env, &asset);
+    let alice = market.master.payer(&str(&env, "alice"));
+    market.add_payer("bob");
+    market.master.transfer(
+        &order,
+        &str(&env, "t1"),
+        &str(&env, "alice"),
+        &str(&env, "bob"),
+        &100,
+        &0,
+    );
+    assert_eq!(order_asset.balance(&alice), 200);
+
+    // anyone expires the order, nothing is refunded without the admin
+    env.ledger().set_timestamp(201);
+    env.set_auths(&[]);
+    market.master.expire_order(&order);
+    assert!(market.master.try_refund_order(&order, &10).is_err());
+    assert_eq!(market.pay_asset.balance(&alice), 0);
+    assert_eq!(order_asset.balance(&alice), 200);
+
+    // the admin refunds the pending transfer and claws back the balance
+    env.mock_all_auths();
+    assert_eq!(market.master.refund_order(&order, &10), 0);
+    assert!(env.auths().iter().any(|(signer, _)| *signer == market.admin));
+    assert_eq!(market.pay_asset.balance(&alice), 300);
+    assert_eq!(order_asset.balance(&alice), 0);
+    let summary = market.master.order_summary(&order);
+    assert_eq!((summary.status, summary.refunded), (OrderStatus::Expired, 300));
+    assert_eq!(
+        market
+            .master
+            .try_mint(&order, &str(&env, "p2"), &str(&env, "alice"), &10, &0, &None),
+        Err(Ok(Error::OrderClosed))
+    );
+}
+
+#[test]
+fn refund_in_pages() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 100);
+    let alice = market.master.payer(&str(&env, "alice"));
+    let bob = market.add_payer("bob");
+    for (payment, payer, amount) in [("p2", "bob", 200), ("p3", "alice", 50)] {
+        market
+            .master
+            .mint(&order, &str(&env, payment), &str(&env, payer), &amount, &0, &None);
+    }
+    market.master.transfer(
+        &order,
+        &str(&env, "t1"),
+        &str(&env, "alice"),
+        &str(&env, "bob"),
+        &30,
+        &0,
+    );
+    env.ledger().set_timestamp(201);
+    market.master.expire_order(&order);
+
+    // the pending transfer and the balance of alice at her first payment
+    assert_eq!(market.master.refund_order(&order, &2), 2);
+    assert_eq!(market.pay_asset.balance(&alice), 150);
+    assert_eq!(market.pay_asset.balance(&bob), 0);
+    assert_eq!(market.master.refund_order(&order, &1), 1);
+    assert_eq!(market.pay_asset.balance(&bob), 200);
+    // alice has nothing left at her second payment
+    assert_eq!(market.master.refund_order(&order, &MAX_REFUNDS), 0);
+    assert_eq!(market.pay_asset.balance(&alice), 150);
+    assert_eq!(market.master.refund_order(&order, &MAX_REFUNDS), 0);
+    assert_eq!(market.master.order_summary(&order).refunded, 350);
+}
+
+#[test]
+fn cancel_refunds_first_page() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 0);
+    let alice = market.master.payer(&str(&env, "alice"));
+    for payment in 0..MAX_REFUNDS + 2 {
+        market.master.mint(
+            &order,
+            &String::from_str(&env, &format!("p{payment}")),
+            &str(&env, "alice"),
+            &10,
+            &0,
+            &None,
+        );
+    }
+
+    assert_eq!(market.master.cancel_order(&order), 2);
+    assert_eq!(market.pay_asset.balance(&alice), 10 * (MAX_REFUNDS as i128 + 2));
+    assert_eq!(market.master.order_summary(&order).status, OrderStatus::Refunded);
+    assert_eq!(market.master.refund_order(&order, &u32::MAX), 0);
+}
+
+#[test]
+fn refund_requires_closed_order() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 300);
+    assert_eq!(
+        market.master.try_refund_order(&order, &10),
+        Err(Ok(Error::InvalidOrderStatus))
+    );
+}
+
+#[test]
+fn deploy_again_with_other_terms() {
+    let env = Env::default();
+    let (market, order) = paid_order(&env, 0);
+    let admin = market.admin.clone();
+
+    let deployed = market
+        .master
+        .deploy(&order, &admin, &None, &None, &None, &None);
+    assert_eq!(
+        market
+            .master
+            .deploy(&order, &admin, &None, &None, &Some(100), &Some(200)),
+        deployed
+    );
+    assert_eq!(
+        market
+            .master
+            .try_deploy(&order, &admin, &None, &None, &Some(150), &None),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(
+        market
+            .master
+            .try_deploy(&order, &admin, &None, &None, &None, &Some(300)),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(
+        market.master.try_deploy(
+            &order,
+            &admin,
+            &None,
+            &Some(market.pay_asset.address.clone()),
+            &None,
+            &None
+        ),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn summary_of_legacy_order_is_read_only() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let order = str(&env, "order");
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+
+    // orders deployed before the state was introduced have no state entry
+    env.as_contract(&market.master.address, || {
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::OrderState(order.clone()))
+    });
+    let summary = market.master.order_summary(&order);
+    assert_eq!(summary.status, OrderStatus::Created);
+    assert_eq!(summary.paid, 0);
+    assert!(!has_state(&env, &market, &order));
+
+    // the state is stored by the first call that changes it
+    market
+        .master
+        .link_order(&order, &Some(str(&env, "buyer")), &None, &None, &None);
+    assert!(has_state(&env, &market, &order));
+    assert_eq!(
+        market.master.order_summary(&order).buyer,
+        Some(str(&env, "buyer"))
+    );
+}
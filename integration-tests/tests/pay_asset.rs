@@ -0,0 +1,191 @@
+use integration_tests::{create_account, Marketplace};
+use master_contract::Error;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::xdr::{AccountId, PublicKey, Uint256};
+use soroban_sdk::{vec, Address, Env, String};
+use std::string::ToString;
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+/// Stellar account of the ed25519 key made of the 'seed' bytes
+fn account(env: &Env, seed: u8) -> Address {
+    let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])));
+    let account = Address::from_str(env, &account_id.to_string());
+    create_account(env, &account);
+    account
+}
+
+/// Deploy the order asset, return its code
+fn deploy(
+    market: &Marketplace,
+    order: &str,
+    issuer: &Address,
+    code: Option<&str>,
+) -> Result<String, Error> {
+    let env = &market.master.env;
+    let code = code.map(|code| str(env, code));
+    market
+        .master
+        .try_deploy(&str(env, order), issuer, &code, &None, &None, &None)
+        .map(|deployed| deployed.unwrap().1)
+        .map_err(|error| error.unwrap())
+}
+
+#[test]
+fn same_code_of_other_issuer() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let code = market.pay_asset.symbol();
+
+    // the default pay asset has the same code, but another issuer
+    let issued = market.master.add_pay_asset(&code, &None);
+    let other = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let bound = market.master.add_pay_asset(&code, &Some(other.address()));
+    assert_eq!(
+        market.master.pay_assets(),
+        vec![&env, market.pay_asset.address.clone(), issued.clone(), bound.clone()]
+    );
+    assert_eq!(
+        market.master.try_add_pay_asset(&code, &None),
+        Err(Ok(Error::AssetAlreadyExists))
+    );
+    assert_eq!(
+        market.master.try_add_pay_asset(&code, &Some(market.pay_asset.address.clone())),
+        Err(Ok(Error::AssetAlreadyExists))
+    );
+
+    market.master.remove_pay_asset(&issued);
+    assert_eq!(
+        market.master.pay_assets(),
+        vec![&env, market.pay_asset.address.clone(), bound]
+    );
+    assert_eq!(
+        market.master.try_remove_pay_asset(&issued),
+        Err(Ok(Error::UnknownPayAsset))
+    );
+}
+
+#[test]
+fn issue_codes_up_to_12_symbols() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    for code in ["E", "EURO", "EUROS", "EUROCENTS123"] {
+        let contract = market.master.add_pay_asset(&str(&env, code), &None);
+        let name = TokenClient::new(&env, &contract).symbol();
+        assert_eq!(name, str(&env, code));
+    }
+    for code in ["", "EUROCENTS1234", "EUR-"] {
+        assert_eq!(
+            market.master.try_add_pay_asset(&str(&env, code), &None),
+            Err(Ok(Error::InvalidAssetCode))
+        );
+    }
+}
+
+#[test]
+fn settle_order_in_added_pay_asset() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let euro = market.master.add_pay_asset(&str(&env, "EUR"), &None);
+    let order = str(&env, "order");
+
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &Some(euro.clone()), &None, &None);
+    assert_eq!(market.master.treasury_balance(&Some(euro)), 0);
+    let unknown = env.register_stellar_asset_contract_v2(market.admin.clone());
+    assert_eq!(
+        market.master.try_deploy(
+            &str(&env, "other"),
+            &market.admin,
+            &None,
+            &Some(unknown.address()),
+            &None,
+            &None
+        ),
+        Err(Ok(Error::UnknownPayAsset))
+    );
+}
+
+#[test]
+fn order_asset_with_requested_code() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    let (contract, code, issuer) = market.master.deploy(
+        &str(&env, "first"),
+        &market.admin,
+        &Some(str(&env, "ORDER1")),
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!((code.clone(), issuer), (str(&env, "ORDER1"), market.admin.clone()));
+    assert_eq!(TokenClient::new(&env, &contract).symbol(), code);
+    assert_eq!(
+        deploy(&market, "second", &market.admin, Some("ORDER1")),
+        Err(Error::AssetAlreadyExists)
+    );
+    // the derived codes skip the requested one
+    assert_ne!(deploy(&market, "third", &market.admin, None), Ok(code));
+
+    for invalid in ["", "ORDERCODE1234", "ORDER-1"] {
+        assert_eq!(
+            deploy(&market, "invalid", &market.admin, Some(invalid)),
+            Err(Error::InvalidAssetCode)
+        );
+    }
+}
+
+#[test]
+fn order_code_of_pay_asset_exists() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    market.master.add_pay_asset(&str(&env, "EUR"), &None);
+
+    assert_eq!(
+        deploy(&market, "order", &market.admin, Some("EUR")),
+        Err(Error::AssetAlreadyExists)
+    );
+    assert_eq!(
+        deploy(&market, "order", &market.admin, Some("EURO")),
+        Ok(str(&env, "EURO"))
+    );
+}
+
+#[test]
+fn order_issuers_allow_list() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let issuer = account(&env, 1);
+
+    assert_eq!(
+        deploy(&market, "first", &issuer, Some("ORDER")),
+        Err(Error::IssuerNotAllowed)
+    );
+    assert_eq!(
+        deploy(&market, "first", &Address::generate(&env), Some("ORDER")),
+        Err(Error::InvalidAddress)
+    );
+
+    market.master.add_issuer(&issuer);
+    assert!(market.master.is_issuer(&issuer));
+    assert!(market.master.is_issuer(&market.admin));
+    // the same code of another issuer is another asset
+    assert_eq!(deploy(&market, "first", &issuer, Some("ORDER")), Ok(str(&env, "ORDER")));
+    assert_eq!(
+        deploy(&market, "second", &market.admin, Some("ORDER")),
+        Ok(str(&env, "ORDER"))
+    );
+
+    market.master.remove_issuer(&issuer);
+    assert!(!market.master.is_issuer(&issuer));
+    assert_eq!(
+        deploy(&market, "third", &issuer, Some("OTHER")),
+        Err(Error::IssuerNotAllowed)
+    );
+}
@@ -0,0 +1,115 @@
+use contract_types::goods::{Price, PriceTier};
+use goods_contract::GoodsError;
+use integration_tests::Marketplace;
+use marketplace_asset::Error;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{vec, Env, Vec};
+
+fn price(market: &Marketplace, amount: i128) -> Price {
+    Price {
+        amount,
+        asset: market.pay_asset.address.clone(),
+        decimals: 2,
+    }
+}
+
+fn tiers(env: &Env, tiers: &[(i128, i128)]) -> Vec<PriceTier> {
+    let mut result = Vec::new(env);
+    for (min_quantity, amount) in tiers {
+        result.push_back(PriceTier {
+            min_quantity: *min_quantity,
+            amount: *amount,
+        });
+    }
+    result
+}
+
+#[test]
+fn quote_at_tier_boundaries() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    market
+        .goods
+        .set_price(&price(&market, 100), &tiers(&env, &[(5, 90), (10, 80)]));
+
+    for (quantity, amount) in [(1, 100), (4, 400), (5, 450), (9, 810), (10, 800), (20, 1600)] {
+        assert_eq!(market.goods.quote(&quantity, &None), price(&market, amount));
+    }
+    assert_eq!(
+        market.goods.try_quote(&0, &None),
+        Err(Ok(GoodsError::NegativeAmount.into()))
+    );
+}
+
+#[test]
+fn tiers_must_increase() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    for invalid in [&[(1, 90)][..], &[(5, 90), (5, 80)], &[(10, 80), (5, 90)]] {
+        assert_eq!(
+            market
+                .goods
+                .try_set_price(&price(&market, 100), &tiers(&env, invalid)),
+            Err(Ok(Error::BadArgs.into()))
+        );
+    }
+    assert_eq!(
+        market
+            .goods
+            .try_set_price(&price(&market, 100), &tiers(&env, &[(5, -1)])),
+        Err(Ok(GoodsError::NegativeAmount.into()))
+    );
+    assert_eq!(market.goods.price().price, price(&market, 100));
+}
+
+#[test]
+fn scheduled_price_applies_at_effective_time() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(1_000);
+
+    market
+        .goods
+        .schedule_price(&price(&market, 120), &vec![&env], &2_000);
+    assert_eq!(market.goods.price().price, price(&market, 100));
+    assert_eq!(market.goods.quote(&2, &Some(1_999)), price(&market, 200));
+    assert_eq!(market.goods.quote(&2, &Some(2_000)), price(&market, 240));
+    assert_eq!(market.goods.price_history().len(), 2);
+
+    // a change at the same time replaces the scheduled one
+    market
+        .goods
+        .schedule_price(&price(&market, 130), &vec![&env], &2_000);
+    assert_eq!(market.goods.price_history().len(), 2);
+
+    env.ledger().set_timestamp(2_000);
+    let current = market.goods.price();
+    assert_eq!((current.price, current.effective_from), (price(&market, 130), 2_000));
+    // the past prices stay in the history for buyers to verify
+    assert_eq!(market.goods.quote(&1, &Some(1_500)), price(&market, 100));
+}
+
+#[test]
+fn cancel_scheduled_price() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(1_000);
+    market
+        .goods
+        .schedule_price(&price(&market, 120), &vec![&env], &2_000);
+
+    assert_eq!(
+        market.goods.try_cancel_price(&3_000),
+        Err(Ok(GoodsError::PriceNotFound.into()))
+    );
+    market.goods.cancel_price(&2_000);
+    assert_eq!(market.goods.price_history().len(), 1);
+    assert_eq!(market.goods.quote(&1, &Some(2_000)), price(&market, 100));
+
+    // the applied price can't be cancelled
+    assert_eq!(
+        market.goods.try_cancel_price(&0),
+        Err(Ok(Error::BadArgs.into()))
+    );
+}
@@ -0,0 +1,65 @@
+use integration_tests::Marketplace;
+use master_contract::OrderStatus;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Env, String};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+#[test]
+fn selected_goods_are_paid_to_seller_by_demand_escrow() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    StellarAssetClient::new(&env, &market.pay_asset.address).mint(&market.buyer, &1000);
+    market.demand.set_pay_asset(&market.pay_asset.address);
+    market.demand.fund(&600);
+    market.master.add_payer(&str(&env, "buyer"), &market.buyer);
+    market.master.add_payer(&str(&env, "seller"), &market.seller);
+
+    // the demand selects goods at the quote of the goods contract,
+    // the seller holds the goods for the buyer
+    market.demand.select_goods(&market.goods.address, &3);
+    let selection = market.demand.selection().unwrap();
+    assert_eq!(selection.amount, market.goods.quote(&3, &None).amount);
+    assert_eq!(selection.seller, market.seller);
+    market.goods.reserve(&market.buyer, &3, &1_000);
+
+    // the master tracks the order of the buyer and the seller
+    let order = str(&env, "order");
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+    market.master.link_order(
+        &order,
+        &Some(str(&env, "buyer")),
+        &Some(str(&env, "seller")),
+        &Some(market.demand.address.clone()),
+        &Some(market.goods.address.clone()),
+    );
+
+    // the goods are delivered and the buyer releases the held amount to the seller
+    StellarAssetClient::new(&env, &market.goods.address).mint(&market.buyer, &3);
+    assert_eq!(market.goods.reserved(), 0);
+    market.goods.fulfil(&market.buyer, &3);
+    assert_eq!(market.goods.sold(), 3);
+    assert_eq!(TokenClient::new(&env, &market.goods.address).balance(&market.buyer), 0);
+    market.demand.release();
+
+    assert_eq!(market.pay_asset.balance(&market.seller), selection.amount);
+    assert_eq!(market.pay_asset.balance(&market.demand.address), 600 - selection.amount);
+    let escrow = market.demand.escrow().unwrap();
+    assert_eq!((escrow.balance, escrow.released), (600 - selection.amount, selection.amount));
+    assert_eq!(market.demand.selection(), None);
+
+    // the order is paid by the escrow, the master pays nothing out
+    let summary = market.master.order_summary(&order);
+    assert_eq!(summary.status, OrderStatus::Created);
+    assert_eq!(summary.paid_out, 0);
+    assert_eq!(summary.demand, Some(market.demand.address.clone()));
+    assert_eq!(summary.goods, Some(market.goods.address.clone()));
+
+    // the buyer withdraws the unused budget
+    market.demand.withdraw(&(600 - selection.amount));
+    assert_eq!(market.pay_asset.balance(&market.buyer), 1000 - selection.amount);
+}
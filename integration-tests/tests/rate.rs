@@ -0,0 +1,117 @@
+use integration_tests::Marketplace;
+use master_contract::{Error, PayoutInfo};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{contract, contractimpl, symbol_short, Env, String};
+
+/// Price feed returning the rate and the timestamp set by the test
+#[contract]
+pub struct PriceFeed;
+
+#[contractimpl]
+impl PriceFeed {
+    pub fn set(env: Env, rate: i128, date: u64) {
+        env.storage().instance().set(&symbol_short!("rate"), &(rate, date));
+    }
+
+    pub fn rate(env: Env, _base: String, _quote: String) -> (i128, u64) {
+        env.storage().instance().get(&symbol_short!("rate")).unwrap()
+    }
+}
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn with_oracle(env: &Env) -> (Marketplace<'_>, PriceFeedClient<'_>) {
+    let market = Marketplace::deploy(env);
+    env.ledger().set_timestamp(10_000);
+    let oracle = PriceFeedClient::new(env, &env.register(PriceFeed, ()));
+    market.master.set_oracle(&Some(oracle.address.clone()));
+    (market, oracle)
+}
+
+#[test]
+fn oracle_rate_from_future_is_rejected() {
+    let env = Env::default();
+    let (market, oracle) = with_oracle(&env);
+    let (usd, code) = (str(&env, "USD"), market.pay_asset.symbol());
+
+    oracle.set(&20_000_000, &10_001);
+    assert_eq!(market.master.try_rate(&usd, &code), Err(Ok(Error::FutureRate)));
+    oracle.set(&20_000_000, &10_000);
+    assert_eq!(market.master.rate(&usd, &code).rate, 20_000_000);
+}
+
+#[test]
+fn oracle_rate_older_than_max_age_is_stale() {
+    let env = Env::default();
+    let (market, oracle) = with_oracle(&env);
+    let (usd, code) = (str(&env, "USD"), market.pay_asset.symbol());
+
+    market.master.set_max_rate_age(&60);
+    oracle.set(&20_000_000, &9_940);
+    assert_eq!(market.master.rate(&usd, &code).date, 9_940);
+    oracle.set(&20_000_000, &9_939);
+    assert_eq!(market.master.try_rate(&usd, &code), Err(Ok(Error::StaleRate)));
+}
+
+#[test]
+fn payout_records_applied_rate() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(10_000);
+    let beneficiary = market.add_payer("bob");
+    market.add_payer("alice");
+    market.fund_treasury(10_000);
+    let order = str(&env, "order");
+
+    market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+    market.master.mint(&order, &str(&env, "payment"), &str(&env, "alice"), &1000, &0, &None);
+    market
+        .master
+        .set_denomination(&order, &str(&env, "USD"));
+    market
+        .master
+        .set_rate(&str(&env, "USD"), &market.pay_asset.symbol(), &20_000_000);
+    for transfer in ["t1", "t2"] {
+        market.master.transfer(
+            &order,
+            &str(&env, transfer),
+            &str(&env, "alice"),
+            &str(&env, "bob"),
+            &500,
+            &10,
+        );
+    }
+
+    market.master.approve_transfer(&order, &str(&env, "t1"));
+    assert_eq!(market.pay_asset.balance(&beneficiary), 980);
+    let payouts = market.master.payouts(&order);
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(
+        payouts.get_unchecked(0),
+        PayoutInfo {
+            transfer: str(&env, "t1"),
+            payer: str(&env, "alice"),
+            beneficiary: str(&env, "bob"),
+            amount: 500,
+            fee: 10,
+            date: Some(10_000),
+            rate: 20_000_000,
+            rate_date: Some(10_000),
+            payout_amount: 980,
+            payout_fee: 20,
+        }
+    );
+
+    // the order paid out 1:1 records the scale as its rate
+    market
+        .master
+        .set_denomination(&order, &market.pay_asset.symbol());
+    market.master.approve_transfer(&order, &str(&env, "t2"));
+    let payout = market.master.payouts(&order).get_unchecked(1);
+    assert_eq!((payout.rate, payout.rate_date), (10_000_000, None));
+    assert_eq!((payout.payout_amount, payout.payout_fee), (490, 10));
+}
@@ -0,0 +1,188 @@
+use integration_tests::Marketplace;
+use marketplace_asset::Error;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{symbol_short, Address, Env, IntoVal, Map, String, Symbol, Val};
+
+// Storage of the schema version 1 is rebuilt from the storage of the freshly deployed
+// smart contracts: every field of the Config goes to its own persistent entry
+
+fn config(env: &Env, contract: &Address) -> Map<Symbol, Val> {
+    env.as_contract(contract, || {
+        let storage = env.storage().instance();
+        let config = storage.get(&symbol_short!("Config")).unwrap();
+        storage.remove(&symbol_short!("Config"));
+        storage.remove(&symbol_short!("Schema"));
+        config
+    })
+}
+
+fn set_v1(env: &Env, contract: &Address, entries: &[(Symbol, Val)]) {
+    env.as_contract(contract, || {
+        for (key, value) in entries {
+            env.storage().persistent().set(key, value);
+        }
+    });
+}
+
+fn has_v1(env: &Env, contract: &Address, key: Symbol) -> bool {
+    env.as_contract(contract, || env.storage().persistent().has(&key))
+}
+
+#[test]
+fn master_migrates_from_v1() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let address = market.master.address.clone();
+    let fee_account = Address::generate(&env);
+
+    let config = config(&env, &address);
+    set_v1(
+        &env,
+        &address,
+        &[
+            (symbol_short!("Admin"), config.get(symbol_short!("admin")).unwrap()),
+            (symbol_short!("PayAsset"), config.get(symbol_short!("pay_asset")).unwrap()),
+            (symbol_short!("FeeAcc"), fee_account.into_val(&env)),
+            (symbol_short!("LastBurn"), 7_u64.into_val(&env)),
+        ],
+    );
+    assert_eq!(market.master.schema_version(), 1);
+    assert_eq!(
+        market.master.try_admin(),
+        Err(Ok(master_contract::Error::SchemaOutdated))
+    );
+
+    assert_eq!(market.master.migrate(), 2);
+    assert_eq!(market.master.schema_version(), 2);
+    assert_eq!(market.master.admin(), market.admin);
+    assert_eq!(market.master.commission_account(), fee_account);
+    assert!(!has_v1(&env, &address, symbol_short!("Admin")));
+    assert!(!has_v1(&env, &address, symbol_short!("LastBurn")));
+}
+
+#[test]
+fn master_payers_and_issuers_require_admin_before_migration() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let address = market.master.address.clone();
+    let alice = String::from_str(&env, "alice");
+    let payer = market.add_payer("alice");
+    let issuer = Address::generate(&env);
+    market.master.add_issuer(&issuer);
+
+    let config = config(&env, &address);
+    set_v1(
+        &env,
+        &address,
+        &[
+            (symbol_short!("Admin"), config.get(symbol_short!("admin")).unwrap()),
+            (symbol_short!("PayAsset"), config.get(symbol_short!("pay_asset")).unwrap()),
+        ],
+    );
+    assert_eq!(market.master.schema_version(), 1);
+
+    // nobody signs for the admin
+    env.set_auths(&[]);
+    let mallory = Address::generate(&env);
+    assert!(market.master.try_add_payer(&alice, &mallory).is_err());
+    assert!(market.master.try_remove_payer(&alice).is_err());
+    assert!(market.master.try_add_issuer(&mallory).is_err());
+    assert!(market.master.try_remove_issuer(&issuer).is_err());
+    assert_eq!(market.master.payer(&alice), payer);
+
+    // the admin of the v1 layout still manages payers until the migration
+    env.mock_all_auths();
+    market.master.add_payer(&alice, &mallory);
+    assert_eq!(env.auths()[0].0, market.admin);
+    assert_eq!(market.master.payer(&alice), mallory);
+}
+
+#[test]
+fn goods_migrates_from_v1() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let address = market.goods.address.clone();
+
+    let config = config(&env, &address);
+    env.as_contract(&address, || {
+        env.storage().persistent().remove(&symbol_short!("Prices"));
+    });
+    set_v1(
+        &env,
+        &address,
+        &[
+            (symbol_short!("id"), config.get(symbol_short!("id")).unwrap()),
+            (symbol_short!("Admin"), config.get(symbol_short!("admin")).unwrap()),
+            (symbol_short!("Name"), config.get(symbol_short!("name")).unwrap()),
+            (symbol_short!("Desc"), config.get(Symbol::new(&env, "description")).unwrap()),
+            (symbol_short!("Price"), 250_u64.into_val(&env)),
+            (symbol_short!("Amount"), 10_u64.into_val(&env)),
+        ],
+    );
+    assert_eq!(market.goods.schema_version(), 1);
+    assert_eq!(
+        market.goods.try_description(),
+        Err(Ok(Error::SchemaOutdated.into()))
+    );
+
+    assert_eq!(market.goods.migrate(), 3);
+    assert_eq!(market.goods.schema_version(), 3);
+    assert_eq!(market.goods.id(), String::from_str(&env, "goods"));
+    assert_eq!(
+        market.goods.description(),
+        String::from_str(&env, "Goods for the integration tests")
+    );
+    assert_eq!(market.goods.amount(), 10);
+    assert_eq!(market.goods.toml_file(), String::from_str(&env, ""));
+    let history = market.goods.price_history();
+    assert_eq!(history.len(), 1);
+    let legacy = history.get_unchecked(0);
+    assert_eq!((legacy.price.amount, legacy.price.decimals, legacy.effective_from), (250, 0, 0));
+    assert_eq!(market.goods.price().price.amount, 250);
+    assert!(!has_v1(&env, &address, symbol_short!("Admin")));
+    assert!(!has_v1(&env, &address, symbol_short!("Price")));
+}
+
+#[test]
+fn demand_migrates_from_v1() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let address = market.demand.address.clone();
+
+    let config = config(&env, &address);
+    set_v1(
+        &env,
+        &address,
+        &[
+            (symbol_short!("id"), config.get(symbol_short!("id")).unwrap()),
+            (symbol_short!("Admin"), config.get(symbol_short!("admin")).unwrap()),
+            (symbol_short!("Name"), config.get(symbol_short!("name")).unwrap()),
+            (symbol_short!("Budget"), 500_u64.into_val(&env)),
+        ],
+    );
+    assert_eq!(market.demand.schema_version(), 1);
+    assert_eq!(
+        market.demand.try_budget(),
+        Err(Ok(Error::SchemaOutdated.into()))
+    );
+
+    assert_eq!(market.demand.migrate(), 2);
+    assert_eq!(market.demand.admin(), market.buyer);
+    assert_eq!(market.demand.name(), String::from_str(&env, "Demand"));
+    assert_eq!(market.demand.description(), String::from_str(&env, ""));
+    assert_eq!(market.demand.budget(), 500);
+    assert!(!has_v1(&env, &address, symbol_short!("Budget")));
+}
+
+#[test]
+fn migrate_current_schema_is_noop() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    assert_eq!(market.master.version(), 3);
+    assert_eq!(market.goods.version(), 1734008630);
+    assert_eq!(market.demand.version(), 1734008630);
+    assert_eq!(market.master.migrate(), 2);
+    assert_eq!(market.goods.migrate(), 3);
+    assert_eq!(market.demand.migrate(), 2);
+}
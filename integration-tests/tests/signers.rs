@@ -0,0 +1,120 @@
+use ed25519_dalek::{Signer, SigningKey};
+use integration_tests::Marketplace;
+use marketplace_asset::auth::{AccSignature, Signers};
+use marketplace_asset::Error;
+use soroban_sdk::auth::{Context, ContractContext};
+use soroban_sdk::token::StellarAssetClient;
+use soroban_sdk::{symbol_short, vec, Address, BytesN, Env, IntoVal, Vec};
+
+fn key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn payload(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[9; 32])
+}
+
+fn signatures(env: &Env, key: &SigningKey) -> Vec<AccSignature> {
+    vec![
+        env,
+        AccSignature {
+            public_key: public_key(env, key),
+            signature: BytesN::from_array(env, &key.sign(&payload(env).to_array()).to_bytes()),
+        },
+    ]
+}
+
+/// Authorization context of the 'transfer' call on the 'contract'
+fn transfer(env: &Env, contract: &Address) -> Vec<Context> {
+    vec![
+        env,
+        Context::Contract(ContractContext {
+            contract: contract.clone(),
+            fn_name: symbol_short!("transfer"),
+            args: vec![env],
+        }),
+    ]
+}
+
+fn check_auth(
+    env: &Env,
+    account: &Address,
+    key: &SigningKey,
+    context: &Vec<Context>,
+) -> Result<(), Result<Error, soroban_sdk::InvokeError>> {
+    env.try_invoke_contract_check_auth::<Error>(
+        account,
+        &payload(env),
+        signatures(env, key).into_val(env),
+        context,
+    )
+}
+
+#[test]
+fn goods_signers_are_set_by_admin() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let keys = vec![&env, public_key(&env, &key(1))];
+
+    assert_eq!(
+        market.goods.try_set_signers(&keys, &2),
+        Err(Ok(Error::BadArgs.into()))
+    );
+    env.set_auths(&[]);
+    assert!(market.goods.try_set_signers(&keys, &1).is_err());
+    assert_eq!(market.goods.signers(), None);
+
+    env.mock_all_auths();
+    market.goods.set_signers(&keys, &1);
+    assert_eq!(env.auths()[0].0, market.seller);
+    assert_eq!(market.goods.signers(), Some(Signers { keys, threshold: 1 }));
+}
+
+#[test]
+fn goods_account_is_signed_by_its_signers() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    let context = transfer(&env, &market.pay_asset.address);
+
+    // nobody signs for the goods contract until the admin sets signers
+    assert_eq!(
+        check_auth(&env, &market.goods.address, &key(1), &context),
+        Err(Ok(Error::NotEnoughSigners))
+    );
+    market.goods.set_signers(&vec![&env, public_key(&env, &key(1))], &1);
+    assert_eq!(
+        check_auth(&env, &market.goods.address, &key(2), &context),
+        Err(Ok(Error::UnknownSigner))
+    );
+    assert_eq!(check_auth(&env, &market.goods.address, &key(1), &context), Ok(()));
+}
+
+#[test]
+fn demand_signers_cant_spend_escrow() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    StellarAssetClient::new(&env, &market.pay_asset.address).mint(&market.buyer, &1000);
+    market.demand.set_pay_asset(&market.pay_asset.address);
+    market.demand.fund(&600);
+    market.demand.set_signers(&vec![&env, public_key(&env, &key(1))], &1);
+    assert_eq!(env.auths()[0].0, market.buyer);
+
+    assert_eq!(
+        check_auth(&env, &market.demand.address, &key(1), &transfer(&env, &market.goods.address)),
+        Ok(())
+    );
+    assert_eq!(
+        check_auth(
+            &env,
+            &market.demand.address,
+            &key(1),
+            &transfer(&env, &market.pay_asset.address)
+        ),
+        Err(Ok(Error::InvalidContext))
+    );
+}
+
@@ -0,0 +1,120 @@
+use integration_tests::Marketplace;
+use marketplace_asset::ttl::{DAY_IN_LEDGERS, PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+use master_contract::{OrderStatus, StorageKey};
+use soroban_sdk::testutils::storage::Persistent;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{vec, Env, String, Vec};
+
+fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn ttl(env: &Env, market: &Marketplace, key: &StorageKey) -> u32 {
+    env.as_contract(&market.master.address, || env.storage().persistent().get_ttl(key))
+}
+
+fn advance(env: &Env, ledgers: u32) {
+    env.ledger().set_sequence_number(env.ledger().sequence() + ledgers);
+}
+
+/// New entries live for 60 days, the contract instances outlive the advances of the tests
+fn ledger(env: &Env) {
+    env.ledger().set_min_persistent_entry_ttl(60 * DAY_IN_LEDGERS);
+}
+
+#[test]
+fn order_entries_extended_on_access() {
+    let env = Env::default();
+    ledger(&env);
+    let market = Marketplace::deploy(&env);
+    market.add_payer("alice");
+    market.add_payer("bob");
+    market.fund_treasury(10_000);
+    market.master.add_issuer(&market.seller);
+    let (order, usd, code) = (str(&env, "order"), str(&env, "USD"), market.pay_asset.symbol());
+
+    let (_, asset_code, issuer) = market.master.deploy(
+        &order,
+        &market.admin,
+        &None,
+        &Some(market.pay_asset.address.clone()),
+        &None,
+        &None,
+    );
+    market.master.set_denomination(&order, &code);
+    market.master.set_rate(&usd, &code, &20_000_000);
+    market.master.mint(&order, &str(&env, "p1"), &str(&env, "alice"), &1000, &0, &None);
+    market.master.transfer(&order, &str(&env, "t1"), &str(&env, "alice"), &str(&env, "bob"), &500, &0);
+
+    let keys: Vec<StorageKey> = vec![
+        &env,
+        StorageKey::Order(order.clone()),
+        StorageKey::OrderState(order.clone()),
+        StorageKey::Settlement(order.clone()),
+        StorageKey::Denomination(order.clone()),
+        StorageKey::Asset(asset_code.clone(), issuer.clone()),
+        StorageKey::Payments(asset_code.clone(), issuer.clone()),
+        StorageKey::Transfers(asset_code.clone(), issuer.clone()),
+        StorageKey::Payer(str(&env, "alice")),
+        StorageKey::Issuer(market.seller.clone()),
+        StorageKey::Rate(usd.clone(), code.clone()),
+    ];
+    for key in keys.iter() {
+        assert_eq!(ttl(&env, &market, &key), PERSISTENT_BUMP_AMOUNT);
+    }
+
+    // the entries are extended by the next access after they fall below the threshold
+    advance(&env, PERSISTENT_BUMP_AMOUNT - PERSISTENT_LIFETIME_THRESHOLD + 1);
+    for key in keys.iter() {
+        assert!(ttl(&env, &market, &key) < PERSISTENT_LIFETIME_THRESHOLD);
+    }
+    market.master.mint(&order, &str(&env, "p2"), &str(&env, "alice"), &100, &0, &None);
+    market.master.transfer(&order, &str(&env, "t2"), &str(&env, "alice"), &str(&env, "bob"), &100, &0);
+    market.master.approve_transfer(&order, &str(&env, "t1"));
+    assert!(market.master.is_issuer(&market.seller));
+    assert_eq!(market.master.rate(&usd, &code).rate, 20_000_000);
+    for key in keys.iter() {
+        assert_eq!(ttl(&env, &market, &key), PERSISTENT_BUMP_AMOUNT);
+    }
+    let payout_records = StorageKey::PayoutRecords(asset_code, issuer);
+    assert_eq!(ttl(&env, &market, &payout_records), PERSISTENT_BUMP_AMOUNT);
+}
+
+#[test]
+fn bumped_entries_outlive_the_extension_period() {
+    let env = Env::default();
+    ledger(&env);
+    let market = Marketplace::deploy(&env);
+    market.add_payer("alice");
+    market.fund_treasury(10_000);
+    market.master.add_issuer(&market.seller);
+    let order = str(&env, "order");
+    let (_, code, issuer) = market
+        .master
+        .deploy(&order, &market.admin, &None, &None, &None, &None);
+    market.master.mint(&order, &str(&env, "payment"), &str(&env, "alice"), &1000, &0, &None);
+
+    let order_keys: Vec<StorageKey> = vec![
+        &env,
+        StorageKey::Order(order.clone()),
+        StorageKey::OrderState(order.clone()),
+        StorageKey::Asset(code.clone(), issuer.clone()),
+        StorageKey::Payments(code, issuer),
+    ];
+    let keys: Vec<StorageKey> = vec![
+        &env,
+        StorageKey::Payer(str(&env, "alice")),
+        StorageKey::Issuer(market.seller.clone()),
+    ];
+
+    // 100 days pass, longer than any entry lives without being extended
+    for _ in 0..5 {
+        advance(&env, 20 * DAY_IN_LEDGERS);
+        market.master.bump_order(&order);
+        market.master.extend_all(&keys);
+    }
+    for key in order_keys.iter().chain(keys.iter()) {
+        assert_eq!(ttl(&env, &market, &key), PERSISTENT_BUMP_AMOUNT);
+    }
+    assert_eq!(market.master.order_summary(&order).status, OrderStatus::Funded);
+}
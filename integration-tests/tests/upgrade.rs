@@ -0,0 +1,47 @@
+use integration_tests::Marketplace;
+use marketplace_asset::upgrade::DEFAULT_UPGRADE_DELAY;
+use marketplace_asset::Error;
+use master_contract::MIN_UPGRADE_DELAY;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::Env;
+
+#[test]
+fn master_upgrade_delay_has_floor() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    assert_eq!(
+        market.master.try_set_upgrade_delay(&(MIN_UPGRADE_DELAY - 1)),
+        Err(Ok(Error::UpgradeDelayTooShort.into()))
+    );
+    assert_eq!(market.master.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+}
+
+#[test]
+fn master_shorter_delay_applies_after_current_one() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+    env.ledger().set_timestamp(1_000);
+
+    market.master.set_upgrade_delay(&MIN_UPGRADE_DELAY);
+    assert_eq!(market.master.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+    env.ledger().set_timestamp(1_000 + DEFAULT_UPGRADE_DELAY - 1);
+    assert_eq!(market.master.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+    env.ledger().set_timestamp(1_000 + DEFAULT_UPGRADE_DELAY);
+    assert_eq!(market.master.upgrade_delay(), MIN_UPGRADE_DELAY);
+
+    // a longer delay applies at once
+    market.master.set_upgrade_delay(&(3 * DEFAULT_UPGRADE_DELAY));
+    assert_eq!(market.master.upgrade_delay(), 3 * DEFAULT_UPGRADE_DELAY);
+}
+
+#[test]
+fn master_longer_delay_drops_pending_reduction() {
+    let env = Env::default();
+    let market = Marketplace::deploy(&env);
+
+    market.master.set_upgrade_delay(&MIN_UPGRADE_DELAY);
+    market.master.set_upgrade_delay(&(2 * DEFAULT_UPGRADE_DELAY));
+    env.ledger().set_timestamp(3 * DEFAULT_UPGRADE_DELAY);
+    assert_eq!(market.master.upgrade_delay(), 2 * DEFAULT_UPGRADE_DELAY);
+}
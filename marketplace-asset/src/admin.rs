@@ -0,0 +1,22 @@
+use crate::error::Error;
+use crate::ttl::Ttl;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Configuration of the smart contract kept in the instance storage
+pub trait AdminConfig: Sized {
+    /// Load the configuration, fails if the smart contract isn't initialized or migrated
+    fn load(env: &Env) -> Result<Self, Error>;
+
+    fn save(&self, env: &Env);
+
+    fn admin(&self) -> &Address;
+
+    /// Load the configuration and require the admin signature,
+    /// panics if the smart contract isn't initialized
+    fn require_admin(env: &Env) -> Self {
+        let config = Self::load(env).unwrap_or_else(|error| panic_with_error!(env, error));
+        config.admin().require_auth();
+        Ttl::extend_instance(env);
+        config
+    }
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use crate::error::Error;
+use soroban_sdk::{
+    contracttype, crypto::Hash, panic_with_error, symbol_short, BytesN, Env, Symbol, Vec,
+};
+
+/// Signers is the keys allowed to sign for the smart contract as an account. Value is a Signers
+pub const SIGNERS: Symbol = symbol_short!("Signers");
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AccSignature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// Ed25519 keys that sign for the smart contract, at least 'threshold' of them must sign
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Signers {
+    pub keys: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+impl Signers {
+    pub fn load(env: &Env) -> Option<Signers> {
+        env.storage().instance().get(&SIGNERS)
+    }
+
+    /// Replace the signers, the threshold must be from 1 to the number of keys.
+    /// No keys remove the signers, then nobody can sign for the smart contract
+    pub fn set(env: &Env, keys: Vec<BytesN<32>>, threshold: u32) {
+        if keys.is_empty() {
+            env.storage().instance().remove(&SIGNERS);
+        } else {
+            if threshold == 0 || threshold > keys.len() {
+                panic_with_error!(env, Error::BadArgs);
+            }
+            for i in 1..keys.len() {
+                if keys.slice(0..i).contains(keys.get_unchecked(i)) {
+                    panic_with_error!(env, Error::BadArgs);
+                }
+            }
+            let signers = Signers {
+                keys: keys.clone(),
+                threshold,
+            };
+            env.storage().instance().set(&SIGNERS, &signers);
+        }
+        env.events()
+            .publish((symbol_short!("signers"),), (keys, threshold));
+    }
+}
+
+/// Verify the signatures of the payload, the signatures must be sorted by the public key,
+/// made by the signers of the smart contract and reach their threshold
+pub fn authenticate(
+    env: &Env,
+    signature_payload: &Hash<32>,
+    signatures: &Vec<AccSignature>,
+) -> Result<(), Error> {
+    let signers = Signers::load(env).ok_or(Error::NotEnoughSigners)?;
+    for i in 0..signatures.len() {
+        let signature = signatures.get_unchecked(i);
+        if i > 0 {
+            let prev_signature = signatures.get_unchecked(i - 1);
+            if prev_signature.public_key >= signature.public_key {
+                return Err(Error::BadSignatureOrder);
+            }
+        }
+        if !signers.keys.contains(&signature.public_key) {
+            return Err(Error::UnknownSigner);
+        }
+        env.crypto().ed25519_verify(
+            &signature.public_key,
+            &signature_payload.clone().into(),
+            &signature.signature,
+        );
+    }
+    if signatures.len() < signers.threshold {
+        return Err(Error::NotEnoughSigners);
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+/// Errors shared by the goods and demand contracts,
+/// codes 11-18 and from 24 are left to errors of the smart contract
+#[contracterror]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    BadSignatureOrder = 2,
+    NotEnoughSigners = 3,
+    InvalidContext = 4,
+    BadArgs = 5,
+    NotInitialized = 6,
+    UnknownSigner = 7,
+    SchemaOutdated = 8,
+    UpgradeNotScheduled = 9,
+    UpgradeTooEarly = 10,
+    MediaNotFound = 19,
+    InvalidMediaBlock = 20,
+    MediaHashMismatch = 21,
+    InvalidMediaRef = 22,
+    UpgradeDelayTooShort = 23,
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+#![no_std]
+
+//! Storage, media, admin, upgrade and authentication shared by the goods and demand contracts
+
+pub mod admin;
+pub mod auth;
+pub mod error;
+pub mod media;
+pub mod metadata;
+pub mod ttl;
+pub mod upgrade;
+
+pub use error::Error;
@@ -0,0 +1,397 @@
+use crate::error::Error;
+use crate::metadata::MediaKey::{Media, MediaBlock, MediaData, MediaHash, MediaLink};
+use crate::metadata::{MediaHashes, MediaInfo, MediaRef, MediaScheme, MediaType, MEDIA_LIST};
+use crate::ttl::Ttl;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, Bytes, BytesN, Env, Map, String, Vec};
+
+/// Max size of a media block in bytes, the block with its key must fit
+/// the 64 KiB limit of a contract data entry on the network
+pub const MAX_BLOCK_SIZE: u32 = 60 * 1024;
+
+/// Max number of blocks of a media, hashes of all blocks are kept in one contract data entry
+/// and all blocks are removed or extended within the budget of one transaction
+pub const MAX_TOTAL_BLOCKS: u64 = 256;
+
+/// Max length of a media reference location and MIME type
+const MAX_REF_LENGTH: u32 = 2048;
+
+/// Number of leading bytes the image type is detected by
+const IMAGE_HEADER_SIZE: usize = 12;
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+pub struct MediaStore;
+
+impl MediaStore {
+    /// Return ids of all media added to the smart contract
+    pub fn media_list(env: &Env) -> Vec<String> {
+        Ttl::extend(env, &MEDIA_LIST);
+        env.storage()
+            .persistent()
+            .get(&MEDIA_LIST)
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub fn media(env: &Env, media_id: &String) -> MediaInfo {
+        let key = Media(media_id.clone());
+        Ttl::extend(env, &key);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::MediaNotFound))
+    }
+
+    /// Return the base64 media block uploaded before binary blocks
+    pub fn media_block(env: &Env, media_id: &String, block_number: u64) -> String {
+        let key = MediaBlock(media_id.clone(), block_number);
+        Ttl::extend(env, &key);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::MediaNotFound))
+    }
+
+    /// Return the media block as bytes, the base64 block uploaded before binary blocks
+    /// is returned as its text
+    pub fn media_chunk(env: &Env, media_id: &String, block_number: u64) -> Bytes {
+        let key = MediaData(media_id.clone(), block_number);
+        if let Some(chunk) = env.storage().persistent().get(&key) {
+            Ttl::extend(env, &key);
+            return chunk;
+        }
+        Self::to_bytes(env, &Self::media_block(env, media_id, block_number))
+    }
+
+    /// Return the reference to the off-chain media content
+    pub fn media_ref(env: &Env, media_id: &String) -> MediaRef {
+        let key = MediaLink(media_id.clone());
+        Ttl::extend(env, &key);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::MediaNotFound))
+    }
+
+    /// Return MIME type of the media, the type of the ImageBase64 image is detected
+    /// from its first block, application/octet-stream is returned if it isn't known
+    pub fn mime_type(env: &Env, media_id: &String) -> String {
+        let media_info = Self::media(env, media_id);
+        if media_info.media_type != MediaType::ImageBase64 {
+            return media_info.media_type.mime_type(env);
+        }
+        let text = if media_info.total_blocks == 0 {
+            Self::to_bytes(env, &media_info.media)
+        } else if env.storage().persistent().has(&MediaData(media_id.clone(), 1))
+            || env.storage().persistent().has(&MediaBlock(media_id.clone(), 1))
+        {
+            Self::media_chunk(env, media_id, 1)
+        } else {
+            return media_info.media_type.mime_type(env);
+        };
+        let mut buf = [0u8; IMAGE_HEADER_SIZE / 3 * 4];
+        let len = text.len().min(buf.len() as u32) as usize;
+        text.slice(0..len as u32).copy_into_slice(&mut buf[..len]);
+        let (header, header_len) = Self::decode_header(&buf[..len]);
+        MediaType::image_type(&header[..header_len])
+            .unwrap_or(MediaType::ImageBase64)
+            .mime_type(env)
+    }
+
+    /// Add the media or replace it with an empty integrity record, blocks of the replaced
+    /// media are removed. The media has at most MAX_TOTAL_BLOCKS blocks. The media without blocks is verified against the media_hash at once,
+    /// the off-chain media has no blocks and its media_hash is the content hash of the reference.
+    /// The data of a binary image starts with the leading bytes of its type
+    pub fn add(
+        env: &Env,
+        media_id: String,
+        media_type: MediaType,
+        media: String,
+        total_blocks: u64,
+        media_hash: BytesN<32>,
+        media_ref: Option<MediaRef>,
+    ) {
+        if media.len() > MAX_BLOCK_SIZE || total_blocks > MAX_TOTAL_BLOCKS {
+            panic_with_error!(env, Error::InvalidMediaBlock)
+        }
+        match &media_ref {
+            Some(media_ref) => {
+                Self::validate_ref(env, media_ref);
+                if total_blocks != 0 {
+                    panic_with_error!(env, Error::InvalidMediaBlock)
+                }
+                if media_ref.content_hash != media_hash {
+                    panic_with_error!(env, Error::MediaHashMismatch)
+                }
+            }
+            None if total_blocks == 0 => {
+                let data = Self::to_bytes(env, &media);
+                if Self::hash(env, &data) != media_hash {
+                    panic_with_error!(env, Error::MediaHashMismatch)
+                }
+                Self::validate_image(env, &media_type, &data);
+            }
+            None => {}
+        }
+        let mut media_list = Self::media_list(env);
+        if !media_list.contains(&media_id) {
+            media_list.push_back(media_id.clone());
+            env.storage().persistent().set(&MEDIA_LIST, &media_list);
+        }
+        if let Some(replaced) = env
+            .storage()
+            .persistent()
+            .get::<_, MediaInfo>(&Media(media_id.clone()))
+        {
+            Self::remove_blocks(env, &media_id, replaced.total_blocks);
+        }
+        let media_info = MediaInfo {
+            media_id: media_id.clone(),
+            media_type,
+            total_blocks,
+            media,
+        };
+        env.storage()
+            .persistent()
+            .set(&Media(media_id.clone()), &media_info);
+        Ttl::extend(env, &Media(media_id.clone()));
+        let hashes = MediaHashes {
+            media_hash,
+            block_hashes: Map::new(env),
+        };
+        env.storage()
+            .persistent()
+            .set(&MediaHash(media_id.clone()), &hashes);
+        Ttl::extend(env, &MediaHash(media_id.clone()));
+        match media_ref {
+            Some(media_ref) => {
+                env.storage()
+                    .persistent()
+                    .set(&MediaLink(media_id.clone()), &media_ref);
+                Ttl::extend(env, &MediaLink(media_id));
+            }
+            None => env.storage().persistent().remove(&MediaLink(media_id)),
+        }
+    }
+
+    /// Store the media block after checking its number, size and SHA-256.
+    /// The media_hash is verified once all blocks are uploaded
+    pub fn upload_block(
+        env: &Env,
+        media_id: String,
+        block_number: u64,
+        block: Bytes,
+        block_hash: BytesN<32>,
+    ) {
+        let media_info = Self::media(env, &media_id);
+        if block_number == 0 || block_number > media_info.total_blocks {
+            panic_with_error!(env, Error::InvalidMediaBlock)
+        }
+        if block.len() > MAX_BLOCK_SIZE {
+            panic_with_error!(env, Error::InvalidMediaBlock)
+        }
+        if Self::hash(env, &block) != block_hash {
+            panic_with_error!(env, Error::MediaHashMismatch)
+        }
+        if block_number == 1 {
+            Self::validate_image(env, &media_info.media_type, &block);
+        }
+        let mut hashes = Self::hashes(env, &media_id);
+        hashes.block_hashes.set(block_number, block_hash);
+        if hashes.block_hashes.len() as u64 == media_info.total_blocks {
+            Self::verify(env, &hashes, media_info.total_blocks);
+        }
+        env.storage()
+            .persistent()
+            .set(&MediaData(media_id.clone(), block_number), &block);
+        Ttl::extend(env, &MediaData(media_id.clone(), block_number));
+        env.storage()
+            .persistent()
+            .set(&MediaHash(media_id.clone()), &hashes);
+        Ttl::extend(env, &MediaHash(media_id));
+    }
+
+    pub fn remove_block(env: &Env, media_id: String, block_number: u64) {
+        env.storage()
+            .persistent()
+            .remove(&MediaData(media_id.clone(), block_number));
+        env.storage()
+            .persistent()
+            .remove(&MediaBlock(media_id.clone(), block_number));
+        let key = MediaHash(media_id);
+        if let Some(mut hashes) = env.storage().persistent().get::<_, MediaHashes>(&key) {
+            hashes.block_hashes.remove(block_number);
+            env.storage().persistent().set(&key, &hashes);
+        }
+    }
+
+    pub fn remove(env: &Env, media_id: String) {
+        if !env.storage().persistent().has(&Media(media_id.clone())) {
+            return;
+        }
+        let media_info = Self::media(env, &media_id);
+        Self::remove_blocks(env, &media_id, media_info.total_blocks);
+        let mut media_list = Self::media_list(env);
+        if let Some(index) = media_list.first_index_of(&media_id) {
+            media_list.remove(index);
+            env.storage().persistent().set(&MEDIA_LIST, &media_list);
+        }
+        env.storage().persistent().remove(&MediaHash(media_id.clone()));
+        env.storage().persistent().remove(&MediaLink(media_id.clone()));
+        env.storage().persistent().remove(&Media(media_id));
+    }
+
+    /// Return true if all blocks of the media are uploaded and verified.
+    /// The media added without hashes is never complete
+    pub fn is_complete(env: &Env, media_id: &String) -> bool {
+        let media_info = Self::media(env, media_id);
+        match env
+            .storage()
+            .persistent()
+            .get::<_, MediaHashes>(&MediaHash(media_id.clone()))
+        {
+            Some(hashes) => hashes.block_hashes.len() as u64 == media_info.total_blocks,
+            None => false,
+        }
+    }
+
+    fn remove_blocks(env: &Env, media_id: &String, total_blocks: u64) {
+        for block in 1..=total_blocks {
+            env.storage()
+                .persistent()
+                .remove(&MediaData(media_id.clone(), block));
+            env.storage()
+                .persistent()
+                .remove(&MediaBlock(media_id.clone(), block));
+        }
+    }
+
+    /// Check the data of a binary image starts with the leading bytes of its type
+    fn validate_image(env: &Env, media_type: &MediaType, data: &Bytes) {
+        if !media_type.is_binary_image() {
+            return;
+        }
+        let mut header = [0u8; IMAGE_HEADER_SIZE];
+        let len = data.len().min(IMAGE_HEADER_SIZE as u32) as usize;
+        data.slice(0..len as u32).copy_into_slice(&mut header[..len]);
+        if MediaType::image_type(&header[..len]).as_ref() != Some(media_type) {
+            panic_with_error!(env, Error::InvalidMediaBlock)
+        }
+    }
+
+    /// Decode the leading base64 characters of the text, decoding stops
+    /// at the padding or at a character out of the base64 alphabet
+    fn decode_header(text: &[u8]) -> ([u8; IMAGE_HEADER_SIZE], usize) {
+        let mut header = [0u8; IMAGE_HEADER_SIZE];
+        let mut len = 0;
+        for quad in text.chunks_exact(4) {
+            let mut bits: u32 = 0;
+            for c in quad {
+                match BASE64.iter().position(|b| b == c) {
+                    Some(value) => bits = bits << 6 | value as u32,
+                    None => return (header, len),
+                }
+            }
+            header[len..len + 3].copy_from_slice(&bits.to_be_bytes()[1..]);
+            len += 3;
+        }
+        (header, len)
+    }
+
+    fn hashes(env: &Env, media_id: &String) -> MediaHashes {
+        env.storage()
+            .persistent()
+            .get(&MediaHash(media_id.clone()))
+            .unwrap_or_else(|| panic_with_error!(env, Error::MediaNotFound))
+    }
+
+    fn verify(env: &Env, hashes: &MediaHashes, total_blocks: u64) {
+        let mut data = Bytes::new(env);
+        for block in 1..=total_blocks {
+            data.append(&hashes.block_hashes.get(block).unwrap().into());
+        }
+        if env.crypto().sha256(&data).to_bytes() != hashes.media_hash {
+            panic_with_error!(env, Error::MediaHashMismatch)
+        }
+    }
+
+    /// Check the CID, Arweave transaction id or URL syntax and the content description
+    fn validate_ref(env: &Env, media_ref: &MediaRef) {
+        let location = &media_ref.location;
+        let mime_type = &media_ref.mime_type;
+        if location.len() > MAX_REF_LENGTH || mime_type.len() > MAX_REF_LENGTH {
+            panic_with_error!(env, Error::InvalidMediaRef)
+        }
+        let mut buf = [0u8; MAX_REF_LENGTH as usize];
+        let location_len = location.len() as usize;
+        location.copy_into_slice(&mut buf[..location_len]);
+        let valid = match media_ref.scheme {
+            MediaScheme::Ipfs => Self::is_cid(&buf[..location_len]),
+            MediaScheme::Arweave => {
+                location_len == 43
+                    && buf[..location_len]
+                        .iter()
+                        .all(|c| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_')
+            }
+            MediaScheme::Https => Self::is_https_url(&buf[..location_len]),
+        };
+        if !valid || media_ref.size == 0 {
+            panic_with_error!(env, Error::InvalidMediaRef)
+        }
+        let mime_type_len = mime_type.len() as usize;
+        mime_type.copy_into_slice(&mut buf[..mime_type_len]);
+        let mime_type = &buf[..mime_type_len];
+        let slash = mime_type.iter().position(|c| *c == b'/');
+        let valid = match slash {
+            Some(slash) => {
+                slash > 0
+                    && slash + 1 < mime_type_len
+                    && mime_type.iter().all(|c| c.is_ascii_graphic())
+            }
+            None => false,
+        };
+        if !valid {
+            panic_with_error!(env, Error::InvalidMediaRef)
+        }
+    }
+
+    /// CIDv0 is base58 starting with Qm, CIDv1 is base32 with the b multibase prefix
+    fn is_cid(cid: &[u8]) -> bool {
+        match cid {
+            [b'Q', b'm', ..] if cid.len() == 46 => cid.iter().all(|c| BASE58.contains(c)),
+            [b'b', rest @ ..] if (50..=120).contains(&rest.len()) => {
+                rest.iter().all(|c| BASE32.contains(c))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_https_url(url: &[u8]) -> bool {
+        let Some(rest) = url.strip_prefix(b"https://") else {
+            return false;
+        };
+        let host_len = rest
+            .iter()
+            .position(|c| matches!(c, b'/' | b'?' | b'#'))
+            .unwrap_or(rest.len());
+        host_len > 0
+            && rest[..host_len]
+                .iter()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'.' | b'-' | b':'))
+            && rest.iter().all(|c| c.is_ascii_graphic())
+    }
+
+    fn hash(env: &Env, data: &Bytes) -> BytesN<32> {
+        env.crypto().sha256(data).to_bytes()
+    }
+
+    /// XDR of the string value is its type and length followed by the string bytes
+    fn to_bytes(env: &Env, data: &String) -> Bytes {
+        if data.len() > MAX_BLOCK_SIZE {
+            panic_with_error!(env, Error::InvalidMediaBlock)
+        }
+        data.clone().to_xdr(env).slice(8..8 + data.len())
+    }
+}
\ No newline at end of file
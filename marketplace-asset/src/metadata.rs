@@ -0,0 +1,121 @@
+use soroban_sdk::{contracttype, symbol_short, BytesN, Env, Map, String, Symbol};
+
+/// MediaList is a list of media_id added to the smart contract. Value is an array of string
+pub const MEDIA_LIST: Symbol = symbol_short!("MediaList");
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MediaType {
+    ImageBase64 = 1,
+    ImageLink = 2,
+    VideoLink = 4,
+    ImagePng = 8,
+    Pdf = 16,
+    Model3d = 32,
+    JsonMetadata = 64,
+    ImageJpeg = 128,
+    ImageGif = 256,
+    ImageWebp = 512,
+}
+
+impl MediaType {
+    /// Return MIME type of the media data. Links are an uri list, the format of the
+    /// ImageBase64 image isn't known without its data, see MediaStore::mime_type
+    pub fn mime_type(&self, env: &Env) -> String {
+        let mime_type = match self {
+            MediaType::ImageBase64 => "application/octet-stream",
+            MediaType::ImagePng => "image/png",
+            MediaType::ImageJpeg => "image/jpeg",
+            MediaType::ImageGif => "image/gif",
+            MediaType::ImageWebp => "image/webp",
+            MediaType::ImageLink | MediaType::VideoLink => "text/uri-list",
+            MediaType::Pdf => "application/pdf",
+            MediaType::Model3d => "model/gltf-binary",
+            MediaType::JsonMetadata => "application/json",
+        };
+        String::from_str(env, mime_type)
+    }
+
+    /// Return the binary image type of the data by its leading bytes
+    pub fn image_type(data: &[u8]) -> Option<MediaType> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(MediaType::ImagePng)
+        } else if data.starts_with(b"\xff\xd8\xff") {
+            Some(MediaType::ImageJpeg)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(MediaType::ImageGif)
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(&b"WEBP"[..]) {
+            Some(MediaType::ImageWebp)
+        } else {
+            None
+        }
+    }
+
+    /// Return true if the media data must start with the leading bytes of its image type
+    pub fn is_binary_image(&self) -> bool {
+        matches!(
+            self,
+            MediaType::ImagePng | MediaType::ImageJpeg | MediaType::ImageGif | MediaType::ImageWebp
+        )
+    }
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MediaInfo {
+    pub media_id: String,
+    pub media_type: MediaType,
+    pub total_blocks: u64,
+    pub media: String,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MediaScheme {
+    Ipfs = 1,
+    Arweave = 2,
+    Https = 3,
+}
+
+/// MediaRef points to the off-chain media content, buyers verify the content
+/// against the content_hash
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MediaRef {
+    pub scheme: MediaScheme,
+    /// CID for IPFS, transaction id for Arweave or URL for HTTPS
+    pub location: String,
+    /// SHA-256 of the content
+    pub content_hash: BytesN<32>,
+    /// content size in bytes
+    pub size: u64,
+    pub mime_type: String,
+}
+
+/// MediaHashes is the integrity record of a media. The media_hash is SHA-256 of the
+/// concatenated block hashes in block order, so it is verified without reading the blocks back
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MediaHashes {
+    pub media_hash: BytesN<32>,
+    /// SHA-256 of every uploaded block by the block number
+    pub block_hashes: Map<u64, BytesN<32>>,
+}
+
+/// Persistent keys of the media, they are encoded as the same named variants
+/// of the StorageKey the smart contracts used before
+#[contracttype]
+#[derive(Clone)]
+pub enum MediaKey {
+    /// Media is representation of a media data. Value is MediaInfo.
+    Media(String),
+    /// MediaBlock is representation of a media data block as a base64 string. Value is String.
+    /// Kept to read the media uploaded before binary blocks
+    MediaBlock(String, u64),
+    /// MediaHash is the integrity record of a media. Value is MediaHashes.
+    MediaHash(String),
+    /// MediaData is a binary media data block. Value is Bytes.
+    MediaData(String, u64),
+    /// MediaLink is a reference to the off-chain media content. Value is MediaRef.
+    MediaLink(String),
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::metadata::MediaKey::{Media, MediaBlock, MediaData, MediaHash, MediaLink};
+use crate::metadata::{MediaInfo, MEDIA_LIST};
+use soroban_sdk::{Env, IntoVal, String, TryFromVal, Val, Vec};
+
+/// Number of ledgers closed per day, a ledger is closed every 5 seconds
+pub const DAY_IN_LEDGERS: u32 = 17280;
+
+/// Instance storage is extended to 30 days when it has less than 29 days to live
+pub const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Persistent entries are extended to 90 days when they have less than 83 days to live
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 90 * DAY_IN_LEDGERS;
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 7 * DAY_IN_LEDGERS;
+
+pub struct Ttl;
+
+impl Ttl {
+    /// Extend the smart contract instance and its code
+    pub fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Extend the persistent entry if it exists
+    pub fn extend<K>(env: &Env, key: &K)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(
+                key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Extend the media description, its integrity record, reference and all its blocks
+    pub fn extend_media(env: &Env, media_id: &String) {
+        let storage_key = Media(media_id.clone());
+        if let Some(media_info) = env.storage().persistent().get::<_, MediaInfo>(&storage_key) {
+            Self::extend(env, &storage_key);
+            Self::extend(env, &MediaHash(media_id.clone()));
+            Self::extend(env, &MediaLink(media_id.clone()));
+            for block in 1..=media_info.total_blocks {
+                Self::extend(env, &MediaData(media_id.clone(), block));
+                Self::extend(env, &MediaBlock(media_id.clone(), block));
+            }
+        }
+    }
+
+    /// Extend the smart contract instance, all media and the given persistent entries
+    pub fn extend_all<K>(env: &Env, keys: Vec<K>)
+    where
+        K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    {
+        Self::extend_instance(env);
+        Self::extend(env, &MEDIA_LIST);
+        let media_list: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&MEDIA_LIST)
+            .unwrap_or(Vec::new(env));
+        for media_id in media_list.iter() {
+            Self::extend_media(env, &media_id);
+        }
+        for key in keys.iter() {
+            Self::extend(env, &key);
+        }
+    }
+}
\ No newline at end of file
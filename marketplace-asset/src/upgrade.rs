@@ -0,0 +1,121 @@
+use crate::error::Error;
+use soroban_sdk::{contracttype, panic_with_error, symbol_short, Address, BytesN, Env, Symbol};
+
+/// Upgrade is an upgrade scheduled by the admin. Value is a PendingUpgrade
+pub const UPGRADE: Symbol = symbol_short!("Upgrade");
+
+/// UpgDelay is a minimal delay between scheduling and execution of the upgrade in seconds.
+/// Value is an u64
+pub const UPGRADE_DELAY: Symbol = symbol_short!("UpgDelay");
+
+/// UpgDelayP is a shorter upgrade delay set by the admin, it applies after the current delay.
+/// Value is a PendingDelay
+pub const PENDING_UPGRADE_DELAY: Symbol = symbol_short!("UpgDelayP");
+
+/// Default delay between scheduling and execution of the upgrade, 2 days in seconds
+pub const DEFAULT_UPGRADE_DELAY: u64 = 2 * 24 * 60 * 60;
+
+/// Minimal delay the admin can set, 1 day in seconds
+pub const MIN_UPGRADE_DELAY: u64 = 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub hash: BytesN<32>,
+    /// ledger timestamp the upgrade can be executed after
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PendingDelay {
+    pub delay: u64,
+    /// ledger timestamp the delay applies after
+    pub eta: u64,
+}
+
+/// Timelocked upgrade of the smart contract code
+pub trait Upgradeable {
+    /// Return the admin allowed to upgrade. The admin of any schema version can upgrade,
+    /// so a failed migration can be fixed by a new code
+    fn upgrade_admin(env: &Env) -> Result<Address, Error>;
+
+    fn require_admin(env: &Env) {
+        Self::upgrade_admin(env)
+            .unwrap_or_else(|error| panic_with_error!(env, error))
+            .require_auth();
+    }
+
+    /// Return the delay in effect, the shorter delay applies when its time comes
+    fn upgrade_delay(env: &Env) -> u64 {
+        let storage = env.storage().instance();
+        match storage.get::<_, PendingDelay>(&PENDING_UPGRADE_DELAY) {
+            Some(pending) if env.ledger().timestamp() >= pending.eta => pending.delay,
+            _ => storage.get(&UPGRADE_DELAY).unwrap_or(DEFAULT_UPGRADE_DELAY),
+        }
+    }
+
+    /// Set the delay, it can't be shorter than MIN_UPGRADE_DELAY. A longer delay applies
+    /// at once, a shorter one applies after the current delay, so upgrades can't be sped up
+    /// right away. Upgrades already scheduled keep their time of execution
+    fn set_upgrade_delay(env: Env, delay: u64) {
+        Self::require_admin(&env);
+        if delay < MIN_UPGRADE_DELAY {
+            panic_with_error!(&env, Error::UpgradeDelayTooShort);
+        }
+        let current = Self::upgrade_delay(&env);
+        let storage = env.storage().instance();
+        storage.remove(&PENDING_UPGRADE_DELAY);
+        let eta = if delay >= current {
+            storage.set(&UPGRADE_DELAY, &delay);
+            env.ledger().timestamp()
+        } else {
+            let eta = env.ledger().timestamp().saturating_add(current);
+            storage.set(&UPGRADE_DELAY, &current);
+            storage.set(&PENDING_UPGRADE_DELAY, &PendingDelay { delay, eta });
+            eta
+        };
+        env.events()
+            .publish((symbol_short!("upgrade"), symbol_short!("delay")), (delay, eta));
+    }
+
+    fn pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        env.storage().instance().get(&UPGRADE)
+    }
+
+    /// Schedule the upgrade to the 'hash' at the 'eta' ledger timestamp,
+    /// the upgrade scheduled before is replaced
+    fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64) {
+        Self::require_admin(&env);
+        if eta < env.ledger().timestamp().saturating_add(Self::upgrade_delay(&env)) {
+            panic_with_error!(&env, Error::UpgradeTooEarly);
+        }
+        let pending = PendingUpgrade { hash: hash.clone(), eta };
+        env.storage().instance().set(&UPGRADE, &pending);
+        env.events()
+            .publish((symbol_short!("upgrade"), symbol_short!("schedule")), (hash, eta));
+    }
+
+    fn cancel_upgrade(env: Env) {
+        Self::require_admin(&env);
+        let pending = Self::pending_upgrade(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::UpgradeNotScheduled));
+        env.storage().instance().remove(&UPGRADE);
+        env.events()
+            .publish((symbol_short!("upgrade"), symbol_short!("cancel")), pending.hash);
+    }
+
+    /// Execute the scheduled upgrade when its time comes
+    fn execute_upgrade(env: Env) {
+        Self::require_admin(&env);
+        let pending = Self::pending_upgrade(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::UpgradeNotScheduled));
+        if env.ledger().timestamp() < pending.eta {
+            panic_with_error!(&env, Error::UpgradeTooEarly);
+        }
+        env.storage().instance().remove(&UPGRADE);
+        env.events()
+            .publish((symbol_short!("upgrade"), symbol_short!("execute")), pending.hash.clone());
+        env.deployer().update_current_contract_wasm(pending.hash);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+mod common;
+
+use common::deploy;
+use ed25519_dalek::{Signer, SigningKey};
+use marketplace_asset::auth::{AccSignature, Signers};
+use marketplace_asset::Error;
+use soroban_sdk::{vec, Bytes, BytesN, Env, Vec};
+
+const PAYLOAD: &[u8] = b"payload";
+
+fn key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn sign(env: &Env, key: &SigningKey) -> AccSignature {
+    let hash = env.crypto().sha256(&Bytes::from_slice(env, PAYLOAD)).to_array();
+    AccSignature {
+        public_key: public_key(env, key),
+        signature: BytesN::from_array(env, &key.sign(&hash).to_bytes()),
+    }
+}
+
+/// Signatures of the keys sorted by the public key
+fn signatures(env: &Env, keys: &[&SigningKey]) -> Vec<AccSignature> {
+    let mut keys = keys.to_vec();
+    keys.sort_by_key(|key| key.verifying_key().to_bytes());
+    let mut signatures = Vec::new(env);
+    for key in keys {
+        signatures.push_back(sign(env, key));
+    }
+    signatures
+}
+
+#[test]
+fn set_signers_checks_threshold_and_duplicates() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let keys = vec![&env, public_key(&env, &key(1)), public_key(&env, &key(2))];
+
+    assert_eq!(
+        contract.try_set_signers(&keys, &0),
+        Err(Ok(Error::BadArgs.into()))
+    );
+    assert_eq!(
+        contract.try_set_signers(&keys, &3),
+        Err(Ok(Error::BadArgs.into()))
+    );
+    let duplicates = vec![&env, public_key(&env, &key(1)), public_key(&env, &key(1))];
+    assert_eq!(
+        contract.try_set_signers(&duplicates, &1),
+        Err(Ok(Error::BadArgs.into()))
+    );
+
+    contract.set_signers(&keys, &2);
+    assert_eq!(contract.signers(), Some(Signers { keys, threshold: 2 }));
+    contract.set_signers(&vec![&env], &0);
+    assert_eq!(contract.signers(), None);
+}
+
+#[test]
+fn authenticate_with_threshold_of_signers() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let payload = Bytes::from_slice(&env, PAYLOAD);
+    let (first, second) = (key(1), key(2));
+
+    assert_eq!(
+        contract.try_authenticate(&payload, &signatures(&env, &[&first])),
+        Err(Ok(Error::NotEnoughSigners))
+    );
+    contract.set_signers(
+        &vec![&env, public_key(&env, &first), public_key(&env, &second)],
+        &2,
+    );
+    assert_eq!(
+        contract.try_authenticate(&payload, &signatures(&env, &[&first])),
+        Err(Ok(Error::NotEnoughSigners))
+    );
+    contract.authenticate(&payload, &signatures(&env, &[&first, &second]));
+}
+
+#[test]
+fn authenticate_rejects_unsorted_and_unknown_signatures() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let payload = Bytes::from_slice(&env, PAYLOAD);
+    let (first, second) = (key(1), key(2));
+    contract.set_signers(
+        &vec![&env, public_key(&env, &first), public_key(&env, &second)],
+        &1,
+    );
+
+    let sorted = signatures(&env, &[&first, &second]);
+    let unsorted = vec![&env, sorted.get_unchecked(1), sorted.get_unchecked(0)];
+    assert_eq!(
+        contract.try_authenticate(&payload, &unsorted),
+        Err(Ok(Error::BadSignatureOrder))
+    );
+    assert_eq!(
+        contract.try_authenticate(&payload, &signatures(&env, &[&key(3)])),
+        Err(Ok(Error::UnknownSigner))
+    );
+
+    // the signature made by another key fails verification
+    let mut forged = sign(&env, &first);
+    forged.signature = sign(&env, &second).signature;
+    assert!(contract
+        .try_authenticate(&payload, &vec![&env, forged])
+        .is_err());
+}
@@ -0,0 +1,144 @@
+//! Smart contract built from the shared modules, the tests of the shared code call it
+//! the same way the goods and demand contracts do
+
+#![allow(dead_code)]
+
+use marketplace_asset::auth::{authenticate, AccSignature, Signers};
+use marketplace_asset::media::MediaStore;
+use marketplace_asset::metadata::{MediaInfo, MediaRef, MediaType};
+use marketplace_asset::upgrade::{PendingUpgrade, Upgradeable};
+use marketplace_asset::Error;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+const ADMIN: Symbol = symbol_short!("Admin");
+
+struct UpgradeableContract;
+
+impl Upgradeable for UpgradeableContract {
+    fn upgrade_admin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::NotInitialized)
+    }
+}
+
+#[contract]
+pub struct AssetContract;
+
+#[contractimpl]
+impl AssetContract {
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&ADMIN, &admin);
+    }
+
+    pub fn add_media(
+        env: Env,
+        media_id: String,
+        media_type: MediaType,
+        media: String,
+        total_blocks: u64,
+        media_hash: BytesN<32>,
+        media_ref: Option<MediaRef>,
+    ) {
+        MediaStore::add(&env, media_id, media_type, media, total_blocks, media_hash, media_ref)
+    }
+
+    pub fn upload_block(
+        env: Env,
+        media_id: String,
+        block_number: u64,
+        block: Bytes,
+        block_hash: BytesN<32>,
+    ) {
+        MediaStore::upload_block(&env, media_id, block_number, block, block_hash)
+    }
+
+    pub fn media(env: Env, media_id: String) -> MediaInfo {
+        MediaStore::media(&env, &media_id)
+    }
+
+    pub fn media_chunk(env: Env, media_id: String, block_number: u64) -> Bytes {
+        MediaStore::media_chunk(&env, &media_id, block_number)
+    }
+
+    pub fn media_mime_type(env: Env, media_id: String) -> String {
+        MediaStore::mime_type(&env, &media_id)
+    }
+
+    pub fn media_ref(env: Env, media_id: String) -> MediaRef {
+        MediaStore::media_ref(&env, &media_id)
+    }
+
+    pub fn media_list(env: Env) -> Vec<String> {
+        MediaStore::media_list(&env)
+    }
+
+    pub fn is_complete(env: Env, media_id: String) -> bool {
+        MediaStore::is_complete(&env, &media_id)
+    }
+
+    pub fn remove_media(env: Env, media_id: String) {
+        MediaStore::remove(&env, media_id)
+    }
+
+    pub fn set_signers(env: Env, keys: Vec<BytesN<32>>, threshold: u32) {
+        Signers::set(&env, keys, threshold)
+    }
+
+    pub fn signers(env: Env) -> Option<Signers> {
+        Signers::load(&env)
+    }
+
+    /// Verify the signatures of SHA-256 of the 'payload'
+    pub fn authenticate(
+        env: Env,
+        payload: Bytes,
+        signatures: Vec<AccSignature>,
+    ) -> Result<(), Error> {
+        authenticate(&env, &env.crypto().sha256(&payload), &signatures)
+    }
+
+    pub fn upgrade_delay(env: Env) -> u64 {
+        UpgradeableContract::upgrade_delay(&env)
+    }
+
+    pub fn set_upgrade_delay(env: Env, delay: u64) {
+        UpgradeableContract::set_upgrade_delay(env, delay)
+    }
+
+    pub fn pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        UpgradeableContract::pending_upgrade(env)
+    }
+
+    pub fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64) {
+        UpgradeableContract::schedule_upgrade(env, hash, eta)
+    }
+
+    pub fn cancel_upgrade(env: Env) {
+        UpgradeableContract::cancel_upgrade(env)
+    }
+
+    pub fn execute_upgrade(env: Env) {
+        UpgradeableContract::execute_upgrade(env)
+    }
+}
+
+/// Deploy the smart contract with a generated admin, authorization is mocked
+pub fn deploy(env: &Env) -> AssetContractClient<'_> {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let contract = env.register(AssetContract, (admin,));
+    AssetContractClient::new(env, &contract)
+}
+
+pub fn str(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+pub fn sha256(env: &Env, data: &[u8]) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_slice(env, data)).to_bytes()
+}
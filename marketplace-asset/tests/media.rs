@@ -0,0 +1,337 @@
+mod common;
+
+use common::{deploy, sha256, str};
+use marketplace_asset::media::{MAX_BLOCK_SIZE, MAX_TOTAL_BLOCKS};
+use marketplace_asset::metadata::{MediaHashes, MediaRef, MediaScheme, MediaType};
+use marketplace_asset::Error;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env, Map, String};
+
+/// Hash of the media uploaded in blocks, SHA-256 of the block hashes in block order
+fn media_hash(env: &Env, blocks: &[&[u8]]) -> BytesN<32> {
+    let mut hashes = Bytes::new(env);
+    for block in blocks {
+        hashes.append(&sha256(env, block).into());
+    }
+    env.crypto().sha256(&hashes).to_bytes()
+}
+
+fn media_ref(env: &Env, scheme: MediaScheme, location: &str) -> MediaRef {
+    MediaRef {
+        scheme,
+        location: str(env, location),
+        content_hash: sha256(env, b"content"),
+        size: 7,
+        mime_type: str(env, "image/png"),
+    }
+}
+
+#[test]
+fn add_inline_media_checks_hash() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "logo");
+
+    assert_eq!(
+        contract.try_add_media(
+            &id,
+            &MediaType::ImageBase64,
+            &str(&env, "aGVsbG8="),
+            &0,
+            &sha256(&env, b"other"),
+            &None
+        ),
+        Err(Ok(Error::MediaHashMismatch.into()))
+    );
+    contract.add_media(
+        &id,
+        &MediaType::ImageBase64,
+        &str(&env, "aGVsbG8="),
+        &0,
+        &sha256(&env, b"aGVsbG8="),
+        &None,
+    );
+    assert!(contract.is_complete(&id));
+    assert_eq!(contract.media(&id).media, str(&env, "aGVsbG8="));
+    assert_eq!(contract.media_list().len(), 1);
+}
+
+#[test]
+fn upload_blocks_verifies_media_hash() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "model");
+    let blocks: [&[u8]; 2] = [b"first block", b"second block"];
+    contract.add_media(
+        &id,
+        &MediaType::Model3d,
+        &str(&env, ""),
+        &2,
+        &media_hash(&env, &blocks),
+        &None,
+    );
+
+    let block = Bytes::from_slice(&env, blocks[0]);
+    assert_eq!(
+        contract.try_upload_block(&id, &1, &block, &sha256(&env, b"other")),
+        Err(Ok(Error::MediaHashMismatch.into()))
+    );
+    assert_eq!(
+        contract.try_upload_block(&id, &3, &block, &sha256(&env, blocks[0])),
+        Err(Ok(Error::InvalidMediaBlock.into()))
+    );
+    contract.upload_block(&id, &1, &block, &sha256(&env, blocks[0]));
+    assert!(!contract.is_complete(&id));
+
+    // the last block completes the media only if the media hash matches
+    let wrong = Bytes::from_slice(&env, b"wrong block");
+    assert_eq!(
+        contract.try_upload_block(&id, &2, &wrong, &sha256(&env, b"wrong block")),
+        Err(Ok(Error::MediaHashMismatch.into()))
+    );
+    let block = Bytes::from_slice(&env, blocks[1]);
+    contract.upload_block(&id, &2, &block, &sha256(&env, blocks[1]));
+    assert!(contract.is_complete(&id));
+    assert_eq!(contract.media_chunk(&id, &2), block);
+}
+
+#[test]
+fn binary_image_starts_with_its_leading_bytes() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "image");
+    let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    let gif: &[u8] = b"GIF89a\x01\0\x01\0";
+    contract.add_media(&id, &MediaType::ImagePng, &str(&env, ""), &1, &media_hash(&env, &[png]), &None);
+
+    assert_eq!(
+        contract.try_upload_block(&id, &1, &Bytes::from_slice(&env, gif), &sha256(&env, gif)),
+        Err(Ok(Error::InvalidMediaBlock.into()))
+    );
+    contract.upload_block(&id, &1, &Bytes::from_slice(&env, png), &sha256(&env, png));
+    assert!(contract.is_complete(&id));
+    assert_eq!(contract.media_mime_type(&id), str(&env, "image/png"));
+
+    // the image without blocks is checked when it is added
+    let inline = String::from_bytes(&env, gif);
+    assert_eq!(
+        contract.try_add_media(&id, &MediaType::ImageJpeg, &inline, &0, &sha256(&env, gif), &None),
+        Err(Ok(Error::InvalidMediaBlock.into()))
+    );
+    contract.add_media(&id, &MediaType::ImageGif, &inline, &0, &sha256(&env, gif), &None);
+    assert_eq!(contract.media_mime_type(&id), str(&env, "image/gif"));
+}
+
+#[test]
+fn base64_image_type_detected_from_data() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "logo");
+
+    for (data, mime_type) in [
+        ("iVBORw0KGgoAAAANSUhEUgAA", "image/png"),
+        ("/9j/4AAQSkZJRgABAQ==", "image/jpeg"),
+        ("UklGRiQAAABXRUJQVlA4", "image/webp"),
+        ("aGVsbG8=", "application/octet-stream"),
+    ] {
+        let media = str(&env, data);
+        let hash = sha256(&env, data.as_bytes());
+        contract.add_media(&id, &MediaType::ImageBase64, &media, &0, &hash, &None);
+        assert_eq!(contract.media_mime_type(&id), str(&env, mime_type));
+    }
+    contract.add_media(&id, &MediaType::ImageBase64, &str(&env, ""), &1, &sha256(&env, b""), &None);
+    assert_eq!(contract.media_mime_type(&id), str(&env, "application/octet-stream"));
+    contract.add_media(&id, &MediaType::Pdf, &str(&env, ""), &1, &sha256(&env, b""), &None);
+    assert_eq!(contract.media_mime_type(&id), str(&env, "application/pdf"));
+}
+
+#[test]
+fn upload_block_limits_size() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "video");
+    contract.add_media(
+        &id,
+        &MediaType::ImagePng,
+        &str(&env, ""),
+        &1,
+        &sha256(&env, b""),
+        &None,
+    );
+
+    let block = Bytes::from_slice(&env, &[1; MAX_BLOCK_SIZE as usize + 1]);
+    assert_eq!(
+        contract.try_upload_block(&id, &1, &block, &env.crypto().sha256(&block).to_bytes()),
+        Err(Ok(Error::InvalidMediaBlock.into()))
+    );
+}
+
+#[test]
+fn total_blocks_limited() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "video");
+
+    assert_eq!(
+        contract.try_add_media(
+            &id,
+            &MediaType::ImagePng,
+            &str(&env, ""),
+            &(MAX_TOTAL_BLOCKS + 1),
+            &sha256(&env, b""),
+            &None
+        ),
+        Err(Ok(Error::InvalidMediaBlock.into()))
+    );
+    contract.add_media(
+        &id,
+        &MediaType::ImagePng,
+        &str(&env, ""),
+        &MAX_TOTAL_BLOCKS,
+        &sha256(&env, b""),
+        &None,
+    );
+    env.cost_estimate().budget().reset_default();
+    contract.remove_media(&id);
+    assert!(contract.media_list().is_empty());
+
+    // hashes of all blocks fit one contract data entry
+    let mut block_hashes = Map::new(&env);
+    for block in 1..=MAX_TOTAL_BLOCKS {
+        block_hashes.set(block, sha256(&env, &block.to_be_bytes()));
+    }
+    let hashes = MediaHashes {
+        media_hash: sha256(&env, b""),
+        block_hashes,
+    };
+    assert!(hashes.to_xdr(&env).len() < 64 * 1024);
+}
+
+#[test]
+fn add_again_removes_old_blocks() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "model");
+    let blocks: [&[u8]; 2] = [b"first block", b"second block"];
+    contract.add_media(
+        &id,
+        &MediaType::Model3d,
+        &str(&env, ""),
+        &2,
+        &media_hash(&env, &blocks),
+        &None,
+    );
+    for (number, block) in blocks.iter().enumerate() {
+        contract.upload_block(
+            &id,
+            &(number as u64 + 1),
+            &Bytes::from_slice(&env, block),
+            &sha256(&env, block),
+        );
+    }
+
+    let replaced: [&[u8]; 1] = [b"new block"];
+    contract.add_media(
+        &id,
+        &MediaType::Model3d,
+        &str(&env, ""),
+        &1,
+        &media_hash(&env, &replaced),
+        &None,
+    );
+    assert!(!contract.is_complete(&id));
+    for number in 1..=2 {
+        assert_eq!(
+            contract.try_media_chunk(&id, &number),
+            Err(Ok(Error::MediaNotFound.into()))
+        );
+    }
+    assert_eq!(contract.media_list().len(), 1);
+}
+
+#[test]
+fn remove_media_with_blocks() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "doc");
+    let blocks: [&[u8]; 1] = [b"pdf"];
+    contract.add_media(
+        &id,
+        &MediaType::Pdf,
+        &str(&env, ""),
+        &1,
+        &media_hash(&env, &blocks),
+        &None,
+    );
+    contract.upload_block(&id, &1, &Bytes::from_slice(&env, blocks[0]), &sha256(&env, blocks[0]));
+
+    contract.remove_media(&id);
+    assert!(contract.media_list().is_empty());
+    assert_eq!(
+        contract.try_media(&id),
+        Err(Ok(Error::MediaNotFound.into()))
+    );
+    assert_eq!(
+        contract.try_media_chunk(&id, &1),
+        Err(Ok(Error::MediaNotFound.into()))
+    );
+}
+
+#[test]
+fn add_media_ref_validates_location() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    let id = str(&env, "photo");
+    let content_hash = sha256(&env, b"content");
+
+    let valid = [
+        media_ref(&env, MediaScheme::Ipfs, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+        media_ref(&env, MediaScheme::Arweave, "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"),
+        media_ref(&env, MediaScheme::Https, "https://example.com/photo.png"),
+    ];
+    for media_ref in valid {
+        contract.add_media(
+            &id,
+            &MediaType::ImageLink,
+            &str(&env, ""),
+            &0,
+            &content_hash,
+            &Some(media_ref.clone()),
+        );
+        assert_eq!(contract.media_ref(&id), media_ref);
+    }
+
+    let invalid = [
+        media_ref(&env, MediaScheme::Ipfs, "QmShort"),
+        media_ref(&env, MediaScheme::Arweave, "not an arweave id"),
+        media_ref(&env, MediaScheme::Https, "http://example.com/photo.png"),
+        MediaRef {
+            mime_type: str(&env, "image"),
+            ..media_ref(&env, MediaScheme::Https, "https://example.com/photo.png")
+        },
+    ];
+    for media_ref in invalid {
+        assert_eq!(
+            contract.try_add_media(
+                &id,
+                &MediaType::ImageLink,
+                &str(&env, ""),
+                &0,
+                &content_hash,
+                &Some(media_ref)
+            ),
+            Err(Ok(Error::InvalidMediaRef.into()))
+        );
+    }
+    assert_eq!(
+        contract.try_add_media(
+            &id,
+            &MediaType::ImageLink,
+            &str(&env, ""),
+            &0,
+            &sha256(&env, b"other"),
+            &Some(media_ref(&env, MediaScheme::Https, "https://example.com/photo.png"))
+        ),
+        Err(Ok(Error::MediaHashMismatch.into()))
+    );
+}
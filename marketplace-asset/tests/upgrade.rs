@@ -0,0 +1,79 @@
+mod common;
+
+use common::deploy;
+use marketplace_asset::upgrade::{PendingUpgrade, DEFAULT_UPGRADE_DELAY, MIN_UPGRADE_DELAY};
+use marketplace_asset::Error;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{BytesN, Env};
+
+#[test]
+fn schedule_upgrade_after_delay() {
+    let env = Env::default();
+    let contract = deploy(&env);
+    env.ledger().set_timestamp(1_000);
+    let hash = BytesN::from_array(&env, &[1; 32]);
+
+    assert_eq!(
+        contract.try_schedule_upgrade(&hash, &(1_000 + DEFAULT_UPGRADE_DELAY - 1)),
+        Err(Ok(Error::UpgradeTooEarly.into()))
+    );
+    let eta = 1_000 + DEFAULT_UPGRADE_DELAY;
+    contract.schedule_upgrade(&hash, &eta);
+    assert_eq!(contract.pending_upgrade(), Some(PendingUpgrade { hash, eta }));
+
+    env.ledger().set_timestamp(eta - 1);
+    assert_eq!(
+        contract.try_execute_upgrade(),
+        Err(Ok(Error::UpgradeTooEarly.into()))
+    );
+}
+
+#[test]
+fn cancel_upgrade() {
+    let env = Env::default();
+    let contract = deploy(&env);
+
+    assert_eq!(
+        contract.try_cancel_upgrade(),
+        Err(Ok(Error::UpgradeNotScheduled.into()))
+    );
+    contract.schedule_upgrade(&BytesN::from_array(&env, &[1; 32]), &DEFAULT_UPGRADE_DELAY);
+    contract.cancel_upgrade();
+    assert_eq!(contract.pending_upgrade(), None);
+    assert_eq!(
+        contract.try_execute_upgrade(),
+        Err(Ok(Error::UpgradeNotScheduled.into()))
+    );
+}
+
+#[test]
+fn upgrade_delay_has_floor() {
+    let env = Env::default();
+    let contract = deploy(&env);
+
+    assert_eq!(
+        contract.try_set_upgrade_delay(&(MIN_UPGRADE_DELAY - 1)),
+        Err(Ok(Error::UpgradeDelayTooShort.into()))
+    );
+    assert_eq!(contract.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+}
+
+#[test]
+fn shorter_delay_applies_after_current_one() {
+    let env = Env::default();
+    let contract = deploy(&env);
+
+    contract.set_upgrade_delay(&MIN_UPGRADE_DELAY);
+    assert_eq!(contract.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY - 1);
+    assert_eq!(contract.upgrade_delay(), DEFAULT_UPGRADE_DELAY);
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY);
+    assert_eq!(contract.upgrade_delay(), MIN_UPGRADE_DELAY);
+
+    // a longer delay applies at once and drops the pending shorter one
+    contract.set_upgrade_delay(&MIN_UPGRADE_DELAY);
+    contract.set_upgrade_delay(&(2 * DEFAULT_UPGRADE_DELAY));
+    assert_eq!(contract.upgrade_delay(), 2 * DEFAULT_UPGRADE_DELAY);
+    env.ledger().set_timestamp(4 * DEFAULT_UPGRADE_DELAY);
+    assert_eq!(contract.upgrade_delay(), 2 * DEFAULT_UPGRADE_DELAY);
+}
@@ -1,23 +1,20 @@
 use soroban_sdk::{Address, Env};
-use crate::store::ADMIN;
+use crate::config::Config;
+use crate::error::Error;
 
 pub struct Admin;
 
 impl Admin {
     /// Return the admin address.
-    pub fn admin(env: Env) -> Address {
-        env.storage().persistent().get(&ADMIN).unwrap()
+    pub fn admin(env: Env) -> Result<Address, Error> {
+        Config::admin(&env)
     }
 
     /// Set the admin.
-    pub fn set_admin(env: Env, new_admin: Address) {
-        if let Some(admin) = env
-            .storage()
-            .persistent()
-            .get::<_, Address>(&ADMIN)
-        {
-            admin.require_auth();
-        };
-        env.storage().persistent().set(&ADMIN, &new_admin);
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let mut config = Config::require_admin(&env)?;
+        config.admin = new_admin;
+        config.save(&env);
+        Ok(())
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use soroban_sdk::{auth::{Context, CustomAccountInterface}, contracttype, crypto:
                   symbol_short, Address, BytesN, Env, Symbol, Vec};
 use crate::error::Error;
 use crate::contract::PaymentContract;
-use crate::store::{ADMIN};
+use crate::config::Config;
 
 #[contracttype]
 #[derive(Clone)]
@@ -70,9 +70,9 @@ impl CustomAccountInterface for PaymentContract {
                 all_signed,
                 &signatures,
             )?;
-            return Err(Error::BadSignatureOrder);
         }
-        Ok(())
+        // Signers of the master contract are not checked yet, so no invocation is authorized
+        Err(Error::BadSignatureOrder)
     }
 }
 
@@ -107,10 +107,8 @@ fn verify_authorization_policy(
 ) -> Result<(), Error> {
     let contract_context = match context {
         Context::Contract(c) => {
-            if &c.contract == curr_contract {
-                if !all_signed {
-                    return Err(Error::NotEnoughSigners);
-                }
+            if &c.contract == curr_contract && !all_signed {
+                return Err(Error::NotEnoughSigners);
             }
             c
         }
@@ -130,14 +128,7 @@ fn is_admin(env: &Env, signatures: &Vec<AccSignature>) -> Result<(), Error> {
     if signatures.len() != 1 {
         return Err(Error::BadSignatureOrder);
     }
-    if !env
-        .storage()
-        .persistent()
-        .has(&ADMIN)
-    {
-        return Err(Error::NotInitialized);
-    }
-    let admin = env.storage().persistent().get::<_, Address>(&ADMIN).unwrap();
+    let admin = Config::admin(env)?;
     let address = Address::from_string_bytes(
         signatures.get_unchecked(0).public_key.as_ref());
     if admin != address {
@@ -0,0 +1,75 @@
+use soroban_sdk::{Env, String, Vec};
+use crate::config::Config;
+use crate::error::Error;
+use crate::minting::Minter;
+use crate::store::{MintRequest, TransferRequest};
+use crate::transfer::Transfer;
+
+/// Batch operations are atomic, the first failed item reverts the whole batch
+/// and its error is returned, otherwise a result is returned for every item in the same order.
+/// The admin signs the batch once for all items
+pub struct Batch;
+
+impl Batch {
+    /// Mint assets for every request and return the minted amount of each request
+    pub fn mint_batch(env: Env, requests: Vec<MintRequest>) -> Result<Vec<i128>, Error> {
+        if requests.is_empty() {
+            return Err(Error::BadArgs);
+        }
+        Config::require_admin(&env)?;
+        let mut results = Vec::new(&env);
+        for request in requests.iter() {
+            Minter::mint_authorized(
+                env.clone(),
+                request.order,
+                request.payment,
+                request.payer,
+                request.amount,
+                request.fee,
+                request.settlement,
+            )?;
+            results.push_back(request.amount - request.fee);
+        }
+        Ok(results)
+    }
+
+    /// Transfer order assets for every request and return the transferred amount of each request
+    pub fn transfer_batch(env: Env, requests: Vec<TransferRequest>) -> Result<Vec<i128>, Error> {
+        if requests.is_empty() {
+            return Err(Error::BadArgs);
+        }
+        Config::require_admin(&env)?;
+        let mut results = Vec::new(&env);
+        for request in requests.iter() {
+            Transfer::transfer_authorized(
+                env.clone(),
+                request.order,
+                request.transfer,
+                request.payer,
+                request.beneficiary,
+                request.amount,
+                request.fee,
+            )?;
+            results.push_back(request.amount);
+        }
+        Ok(results)
+    }
+
+    /// Approve every transfer of the order and return the amount paid out
+    /// in the settlement pay asset for each transfer
+    pub fn approve_transfers(
+        env: Env,
+        order: String,
+        transfers: Vec<String>,
+    ) -> Result<Vec<i128>, Error> {
+        if transfers.is_empty() {
+            return Err(Error::BadArgs);
+        }
+        Config::require_admin(&env)?;
+        let mut results = Vec::new(&env);
+        for transfer in transfers.iter() {
+            results.push_back(Transfer::approve_authorized(env.clone(), order.clone(), transfer)?);
+        }
+        Ok(results)
+    }
+}
\ No newline at end of file
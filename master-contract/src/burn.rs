@@ -1,8 +1,10 @@
+use crate::config::Config;
 use crate::error::Error;
-use crate::minting::MintClient;
 use crate::payer::Payer;
-use crate::store::StorageKey::{Withdraw};
-use crate::store::{get_pay_asset_info, TransferInfo, ADMIN, LAST_BURN};
+use crate::payout::Payout;
+use crate::store::StorageKey::{Withdraw, WithdrawAsset};
+use crate::store::{get_pay_asset, get_pay_asset_info, get_stored_pay_asset, OrderInfo, TransferInfo};
+use crate::ttl::Ttl;
 use soroban_sdk::{vec, Address, Env, String, Vec};
 use crate::commission::Commission;
 use crate::store;
@@ -10,62 +12,82 @@ use crate::store;
 pub struct Burn;
 
 impl Burn {
+    /// Store the pending withdrawal, the list keeps one record per 'payout'
     fn update_withdraw_records(env: &Env, transfer_info: TransferInfo) -> Result<(), Error> {
-        let storage_key = Withdraw(transfer_info.clone().transfer);
-        let withdraw = if env.storage().persistent().has(&storage_key) {
-            let mut existing_transfers: Vec<TransferInfo> =
-                env.storage().persistent().get(&storage_key).unwrap();
-            existing_transfers.push_back(transfer_info);
-            existing_transfers
-        } else {
-            vec![env, transfer_info]
-        };
-        env.storage().persistent().set(&storage_key, &withdraw);
+        let storage_key = Withdraw(transfer_info.transfer.clone());
+        env.storage().persistent().set(&storage_key, &vec![env, transfer_info]);
         Ok(())
     }
+    /// Return the pay asset burned for the withdrawal 'payout'
+    fn get_withdraw_asset(env: &Env, payout: &String) -> Result<OrderInfo, Error> {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&WithdrawAsset(payout.clone()))
+        {
+            Some(contract) => get_stored_pay_asset(env, &contract),
+            None => get_pay_asset_info(env),
+        }
+    }
+
     /// Calls the 'burn' function of the 'contract' with 'amount' to burn payer assets.
+    /// The default pay asset is burned if the 'pay_asset' contract is not given
     pub fn burn(
         env: Env,
         from: String,
         payout: String,
         amount: i128,
         fee: i128,
+        pay_asset: Option<Address>,
     ) -> Result<(), Error> {
         // Verify the amount is positive after commission deduction.
         if amount - fee < 0 {
             return Err(Error::NegativeAmount);
         }
         
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
+        Config::require_admin(&env)?;
+        Ttl::extend_config(&env);
 
         if env.storage().persistent().has(&Withdraw(payout.clone())) {
             return Err(Error::AlreadyInitialized);
         }
         let transfer_info = TransferInfo {
-            transfer: payout,
+            transfer: payout.clone(),
             payer: from.clone(),
             beneficiary: from.clone(),
             amount,
             fee,
             date: Option::from(env.ledger().timestamp()),
         };
+        let pay_asset = match pay_asset {
+            Some(contract) => {
+                let pay_asset = get_pay_asset(&env, &contract)?;
+                env.storage().persistent().set(&WithdrawAsset(payout.clone()), &contract);
+                pay_asset
+            }
+            None => get_pay_asset_info(&env)?,
+        };
         Self::update_withdraw_records(&env, transfer_info)?;
-        let pay_asset = get_pay_asset_info(&env)?;
-        let client = MintClient::new(&env, &pay_asset.contract);
-        let from_acc = Payer::payer(env.clone(), from);
+        let from_acc = Payer::payer(env.clone(), from)?;
 
-        client.clawback(&from_acc, &(amount));
+        Payout::collect(&env, &pay_asset, &from_acc, &(amount));
         Ok(())
     }
     
     fn get_withdraw_records(env: &Env, payout: String) -> Result<TransferInfo, Error> {
         let storage_key = Withdraw(payout);
-        let withdraw = env.storage().persistent().get(&storage_key).unwrap();
-        Ok(withdraw)
+        env.storage()
+            .persistent()
+            .get::<_, Vec<TransferInfo>>(&storage_key)
+            .and_then(|records| records.first())
+            .ok_or(Error::WithdrawNotFound)
     }
     
     fn delete_withdraw_records(env: &Env, payout: String) -> Result<(), Error> {
+        let asset_key = WithdrawAsset(payout.clone());
+        if env.storage().persistent().has(&asset_key) {
+            env.storage().persistent().remove(&asset_key);
+        }
         let storage_key = Withdraw(payout);
         if env.storage().persistent().has(&storage_key) {
             env.storage().persistent().remove(&storage_key);
@@ -74,30 +96,31 @@ impl Burn {
     }
     
     pub fn approve_burn(env: Env, payout: String) -> Result<(), Error> {
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
+        Config::require_admin(&env)?;
+        Ttl::extend_config(&env);
 
 
         let withdraw_record = Self::get_withdraw_records(&env, payout.clone())?;
-        let _ = Commission::pay_commission(env.clone(), &withdraw_record.fee);
+        let pay_asset = Self::get_withdraw_asset(&env, &payout)?;
+        let _ = Commission::pay_commission(env.clone(), &pay_asset, &withdraw_record.fee);
      
-        let last_burn = env.storage().persistent().get(&LAST_BURN).unwrap_or(0u64);
-        env.storage().persistent().set(&store::StorageKey::Burn(last_burn), &withdraw_record);
-        env.storage().persistent().set(&LAST_BURN, &(last_burn + 1));
+        let mut config = Config::load(&env)?;
+        env.storage().persistent().set(&store::StorageKey::Burn(config.last_burn), &withdraw_record);
+        config.last_burn += 1;
+        config.save(&env);
         
         Self::delete_withdraw_records(&env, payout)
     }
     
     pub fn reject_burn(env: Env, payout: String) -> Result<(), Error> {
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
+        Config::require_admin(&env)?;
+        Ttl::extend_config(&env);
 
-        let pay_asset = get_pay_asset_info(&env)?;
-        let client = MintClient::new(&env, &pay_asset.contract);
+        let pay_asset = Self::get_withdraw_asset(&env, &payout)?;
         let withdraw_record = Self::get_withdraw_records(&env, payout.clone())?;
-        let payer_account = Payer::payer(env.clone(), withdraw_record.payer);
+        let payer_account = Payer::payer(env.clone(), withdraw_record.payer)?;
         
-        client.mint(&payer_account, &(withdraw_record.amount));
+        Payout::pay(&env, &pay_asset, &payer_account, &(withdraw_record.amount));
      
         Self::delete_withdraw_records(&env, payout)
     }
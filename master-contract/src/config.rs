@@ -0,0 +1,98 @@
+use soroban_sdk::{contracttype, vec, Address, Env, String, Vec};
+use crate::error::Error;
+use crate::schema::Schema;
+use crate::store::{
+    OrderInfo, PayMode, ADMIN, CONFIG, FEE_ACCOUNT, LAST_ASSET, LAST_BURN, PAY_ASSET,
+};
+
+/// Config is the smart contract configuration kept in the instance storage
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    /// address that authorized to sign the contract
+    pub(crate) admin: Address,
+    /// address that collects fee from smart contract operations
+    pub(crate) fee_account: Option<Address>,
+    /// default pay asset
+    pub(crate) pay_asset: OrderInfo,
+    /// way to pay out the default pay asset
+    pub(crate) pay_mode: PayMode,
+    /// pay assets added besides the default one
+    pub(crate) pay_assets: Vec<OrderInfo>,
+    /// suffix of the last asset code generated for orders
+    pub(crate) last_asset: Option<String>,
+    /// counter of approved burns
+    pub(crate) last_burn: u64,
+    /// price feed smart contract for exchange rates
+    pub(crate) oracle: Option<Address>,
+    /// maximum age of the exchange rate in seconds
+    pub(crate) max_rate_age: Option<u64>,
+}
+
+impl Config {
+    /// Create the configuration for the new smart contract
+    pub fn new(env: &Env, admin: Address, pay_asset: OrderInfo, pay_mode: PayMode) -> Config {
+        Config {
+            admin,
+            fee_account: None,
+            pay_asset,
+            pay_mode,
+            pay_assets: vec![env],
+            last_asset: None,
+            last_burn: 0,
+            oracle: None,
+            max_rate_age: None,
+        }
+    }
+
+    /// Return true if the smart contract is initialized
+    pub fn is_initialized(env: &Env) -> bool {
+        env.storage().instance().has(&CONFIG) || env.storage().persistent().has(&ADMIN)
+    }
+
+    /// Load the configuration, the storage schema must be migrated to the current version
+    pub fn load(env: &Env) -> Result<Config, Error> {
+        Schema::require_current(env)?;
+        env.storage()
+            .instance()
+            .get(&CONFIG)
+            .ok_or(Error::NotInitialized)
+    }
+
+    pub fn save(&self, env: &Env) {
+        env.storage().instance().set(&CONFIG, self);
+    }
+
+    /// Return the admin address
+    pub fn admin(env: &Env) -> Result<Address, Error> {
+        Ok(Self::load(env)?.admin)
+    }
+
+    /// Load the configuration and require the admin signature
+    pub fn require_admin(env: &Env) -> Result<Config, Error> {
+        let config = Self::load(env)?;
+        config.admin.require_auth();
+        Ok(config)
+    }
+
+    /// Move the configuration from separate persistent entries into the instance storage,
+    /// version 1 pays out only by minting the pay asset issued by the admin
+    pub(crate) fn migrate(env: &Env) -> Result<(), Error> {
+        let storage = env.storage().persistent();
+        let admin: Address = storage.get(&ADMIN).ok_or(Error::NotInitialized)?;
+        let pay_asset: OrderInfo = storage.get(&PAY_ASSET).ok_or(Error::NotInitialized)?;
+        let config = Config {
+            fee_account: storage.get(&FEE_ACCOUNT),
+            last_asset: storage.get(&LAST_ASSET),
+            last_burn: storage.get(&LAST_BURN).unwrap_or(0),
+            ..Config::new(env, admin, pay_asset, PayMode::Mint)
+        };
+        for key in [ADMIN, FEE_ACCOUNT, LAST_ASSET, PAY_ASSET, LAST_BURN] {
+            if storage.has(&key) {
+                storage.remove(&key);
+            }
+        }
+        config.save(env);
+        Ok(())
+    }
+}
\ No newline at end of file
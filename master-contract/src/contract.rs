@@ -1,15 +1,27 @@
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, EnvBase, String, Val};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
 use crate::admin::Admin;
+use crate::batch::Batch;
 use crate::burn::Burn;
 use crate::commission::Commission;
+use crate::config::Config;
 use crate::deployer::Deployer;
 use crate::error::{Error};
+use crate::issuer::Issuer;
 use crate::minting::Minter;
+use crate::order::Order;
 use crate::payer::Payer;
-use crate::serialize_xdr::{CPAsset, CPWriteXdr};
+use crate::pay_asset::PayAsset;
+use crate::payout::Payout;
+use crate::rate::Rate;
+use crate::schema::Schema;
 use crate::upgrade::UpgradeableContract;
-use crate::store::{OrderInfo, ADMIN, LAST_BURN, PAY_ASSET};
+use marketplace_asset::upgrade::{PendingUpgrade, Upgradeable};
+use crate::store::{
+    get_pay_asset, get_pay_asset_info, MintRequest, PayoutInfo, RateInfo, StorageKey,
+    TransferRequest,
+};
 use crate::transfer::Transfer;
+use crate::ttl::Ttl;
 
 #[contract]
 pub struct PaymentContract;
@@ -20,51 +32,41 @@ impl PaymentContract {
     ///  code must be aligned within stellar rules of asset names convention,
     /// please check https://developers.stellar.org/docs/tokens/control-asset-access#naming-an-asset
     /// and should be unique for admin address as an issuer of this asset
-    /// the length of asset code must be less than 6 symbols, but have at least one symbol
-    pub fn __constructor(e: Env, admin: Address, pay_asset: String) {
-        let _ = Self::init(e, admin, pay_asset).expect("can't initialize smart contract");
+    /// the asset code has from 1 to 12 symbols, codes of order assets are derived from it
+    /// by a suffix, so order assets need an explicit code if the pay asset code is too long.
+    /// If 'pay_contract' is given, the smart contract binds to the existing asset contract
+    /// with 'pay_asset' code (e.g. USDC) and pays out from its own balance instead of minting
+    pub fn __constructor(e: Env, admin: Address, pay_asset: String, pay_contract: Option<Address>) {
+        Self::init(e, admin, pay_asset, pay_contract).expect("can't initialize smart contract");
     }
 
-    fn init(e: Env, admin: Address, pay_asset: String) -> Result<(), Error> {
-        let length = pay_asset.clone().len();
-        if  length > 5  && length < 1 {
-            return Err(Error::BadArgs);
-        }
-        if e.storage().persistent().has(&ADMIN) {
+    fn init(
+        e: Env,
+        admin: Address,
+        pay_asset: String,
+        pay_contract: Option<Address>,
+    ) -> Result<(), Error> {
+        if Config::is_initialized(&e) {
             return Err(Error::AlreadyInitialized);
         }
-        e.storage().persistent().set(&ADMIN, &admin);
-        let asset_code: &mut [u8; 12] = &mut [0u8; 12];
-        e.string_copy_to_slice(pay_asset.to_object(), Val::U32_ZERO,
-                               asset_code[..(pay_asset.len() as usize)].as_mut()).unwrap();
-        // Convert Symbol to String using the function
-        let asset = CPAsset { code: *asset_code, issuer: admin.to_string() };
-        let asset_serialized = asset.to_xdr(&e).unwrap();
-
-        // Deploy the contract using the uploaded Wasm with given hash.
-        let deployed_address = e
-            .deployer()
-            .with_stellar_asset(asset_serialized.clone())
-            .deploy();
-        // store cash out information
-        let order_key = &OrderInfo {
-            contract: deployed_address.clone(),
-            code: pay_asset.clone(),
-            issuer: admin,
+        let (order_key, pay_mode) = match pay_contract {
+            Some(pay_contract) => PayAsset::bind(&e, &pay_asset, pay_contract)?,
+            None => PayAsset::issue(&e, &admin, &pay_asset)?,
         };
-        e.storage().persistent().set(&PAY_ASSET, order_key);
-        e.storage().persistent().set(&LAST_BURN, &0u64);
+        Config::new(&e, admin, order_key, pay_mode).save(&e);
+        Schema::init(&e);
+        Ttl::extend_config(&e);
 
         Ok(())
     }
 
     /// Get admin address
-    pub fn admin(env: Env) -> Address {
+    pub fn admin(env: Env) -> Result<Address, Error> {
         Admin::admin(env)
     }
 
     /// Set a new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         Admin::set_admin(env, new_admin)
     }
 
@@ -74,20 +76,103 @@ impl PaymentContract {
     }
 
     /// Set a new commission account address
-    pub fn set_commission_account(env: Env, commission_account: Address) {
+    pub fn set_commission_account(env: Env, commission_account: Address) -> Result<(), Error> {
         Commission::set_commission_account(env, commission_account)
     }
 
+    /// Get the pay asset balance held by the smart contract for payouts,
+    /// the default pay asset is used if the 'pay_asset' contract is not given
+    pub fn treasury_balance(env: Env, pay_asset: Option<Address>) -> Result<i128, Error> {
+        let pay_asset = match pay_asset {
+            Some(contract) => get_pay_asset(&env, &contract)?,
+            None => get_pay_asset_info(&env)?,
+        };
+        Ok(Payout::treasury_balance(&env, &pay_asset))
+    }
+
+    /// Get contracts of all pay assets, the default pay asset goes first
+    pub fn pay_assets(env: Env) -> Result<Vec<Address>, Error> {
+        PayAsset::pay_assets(env)
+    }
+
+    /// Add a pay asset that can be chosen as a settlement asset of the order
+    /// a new asset is issued by the admin if 'pay_contract' is not given,
+    /// return the contract of the pay asset that is used to choose it
+    pub fn add_pay_asset(
+        env: Env,
+        code: String,
+        pay_contract: Option<Address>,
+    ) -> Result<Address, Error> {
+        PayAsset::add_pay_asset(env, code, pay_contract)
+    }
+
+    /// Remove a pay asset added by 'add_pay_asset'
+    pub fn remove_pay_asset(env: Env, pay_asset: Address) -> Result<(), Error> {
+        PayAsset::remove_pay_asset(env, pay_asset)
+    }
+
+    /// Get exchange rate of 'base' in 'quote' scaled by 10^7
+    pub fn rate(env: Env, base: String, quote: String) -> Result<RateInfo, Error> {
+        Rate::rate(env, base, quote)
+    }
+
+    /// Set exchange rate of 'base' in 'quote' scaled by 10^7
+    pub fn set_rate(env: Env, base: String, quote: String, rate: i128) -> Result<(), Error> {
+        Rate::set_rate(env, base, quote, rate)
+    }
+
+    /// Set price feed oracle address, the admin rate table is used if it is not set
+    pub fn set_oracle(env: Env, oracle: Option<Address>) -> Result<(), Error> {
+        Rate::set_oracle(env, oracle)
+    }
+
+    /// Set maximum age of exchange rate in seconds
+    pub fn set_max_rate_age(env: Env, max_age: u64) -> Result<(), Error> {
+        Rate::set_max_rate_age(env, max_age)
+    }
+
+    /// Set currency of the order asset, the approved transfers are exchanged
+    /// into the settlement pay asset by the rate of this currency
+    pub fn set_denomination(env: Env, order: String, currency: String) -> Result<(), Error> {
+        Rate::set_denomination(env, order, currency)
+    }
+
     /// Issue asset for the order
+    /// the asset code is derived from the pay asset code if 'code' is not given,
+    /// the issuer must be the admin or an address added by 'add_issuer',
+    /// the order is paid out in the 'settlement' pay asset or in the default one,
+    /// the order can't be paid after 'payment_deadline' and expires if it isn't settled
+    /// until 'delivery_deadline'
     pub fn deploy(
         env: Env,
         order: String,
         issuer: Address,
-    ) -> (Address, String, Address) {
-        Deployer::deploy(env, order, issuer)
+        code: Option<String>,
+        settlement: Option<Address>,
+        payment_deadline: Option<u64>,
+        delivery_deadline: Option<u64>,
+    ) -> Result<(Address, String, Address), Error> {
+        Deployer::deploy(
+            env, order, issuer, code, settlement, payment_deadline, delivery_deadline)
+    }
+
+    /// Check if the address is allowed to issue order assets
+    pub fn is_issuer(env: Env, issuer: Address) -> bool {
+        Issuer::is_allowed(&env, &issuer)
+    }
+
+    /// Add issuer address to the allow-list
+    pub fn add_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        Issuer::add_issuer(env, issuer)
+    }
+
+    /// Remove issuer address from the allow-list
+    pub fn remove_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        Issuer::remove_issuer(env, issuer)
     }
 
     /// Mint asset for the paid order
+    /// the 'settlement' pay asset is used only if the order is created by this call
     pub fn mint(
         env: Env,
         order: String,
@@ -95,8 +180,69 @@ impl PaymentContract {
         payer: String,
         amount: i128,
         fee: i128,
+        settlement: Option<Address>,
+    ) -> Result<(), Error> {
+        Minter::mint(env, order, payment, payer, amount, fee, settlement)
+    }
+
+    /// Get lifecycle state and totals of the order
+    pub fn order_summary(env: Env, order: String) -> Result<Order, Error> {
+        Order::order_summary(env, order)
+    }
+
+    /// Link the order to the buyer and seller ids and the demand and goods smart contracts
+    pub fn link_order(
+        env: Env,
+        order: String,
+        buyer: Option<String>,
+        seller: Option<String>,
+        demand: Option<Address>,
+        goods: Option<Address>,
     ) -> Result<(), Error> {
-        Minter::mint(env, order, payment, payer, amount, fee)
+        Order::link_order(env, order, buyer, seller, demand, goods)
+    }
+
+    /// Cancel the order, reject pending transfers and refund outstanding order asset
+    /// balances in the pay asset, the cancelled order can't be paid anymore.
+    /// Return the number of transfers and payments left for 'refund_order'
+    pub fn cancel_order(env: Env, order: String) -> Result<u32, Error> {
+        Order::cancel_order(env, order)
+    }
+
+    /// Expire the order after its payment or delivery deadline, can be called by anyone.
+    /// The expiry doesn't refund, outstanding balances of the expired order are refunded
+    /// by the admin with 'refund_order'
+    pub fn expire_order(env: Env, order: String) -> Result<(), Error> {
+        Order::expire_order(env, order)
+    }
+
+    /// Reject pending transfers and refund outstanding order asset balances
+    /// of the expired or cancelled order in the pay asset, at most 'limit' per call.
+    /// Return the number of transfers and payments left
+    pub fn refund_order(env: Env, order: String, limit: u32) -> Result<u32, Error> {
+        Order::refund_order(env, order, limit)
+    }
+
+    /// Mint assets for the batch of paid orders
+    /// the batch is atomic, minted amounts are returned for every request
+    pub fn mint_batch(env: Env, requests: Vec<MintRequest>) -> Result<Vec<i128>, Error> {
+        Batch::mint_batch(env, requests)
+    }
+
+    /// Transfer order assets for the batch of payments to beneficiaries
+    /// the batch is atomic, transferred amounts are returned for every request
+    pub fn transfer_batch(env: Env, requests: Vec<TransferRequest>) -> Result<Vec<i128>, Error> {
+        Batch::transfer_batch(env, requests)
+    }
+
+    /// Approve the batch of order asset transfers
+    /// the batch is atomic, paid out amounts are returned for every transfer
+    pub fn approve_transfers(
+        env: Env,
+        order: String,
+        transfers: Vec<String>,
+    ) -> Result<Vec<i128>, Error> {
+        Batch::approve_transfers(env, order, transfers)
     }
 
     /// Transfer order asset as a payment to the beneficiary
@@ -122,6 +268,11 @@ impl PaymentContract {
         Transfer::approve_transfer(env, order, transfer)
     }
 
+    /// Get payouts of the order with the rates applied to the approved transfers
+    pub fn payouts(env: Env, order: String) -> Result<Vec<PayoutInfo>, Error> {
+        Transfer::payouts(env, order)
+    }
+
     /// Reject order asset transfer
     /// will do revert order asset to the order payer
     pub fn reject_transfer(
@@ -132,15 +283,19 @@ impl PaymentContract {
         Transfer::reject_transfer(env, order, transfer)
     }
 
-    /// Burn order asset
+    /// Burn pay asset for the withdrawal payout
+    /// the default pay asset is burned if 'pay_asset' is not given.
+    /// A pay asset issued by the admin is clawed back with the admin signature,
+    /// a bound pay asset is transferred to the treasury and the payer must sign the transfer
     pub fn burn(
         env: Env,
         from: String,
         payout: String,
         amount: i128,
         fee: i128,
+        pay_asset: Option<Address>,
     ) -> Result<(), Error> {
-        Burn::burn(env, from, payout, amount, fee)
+        Burn::burn(env, from, payout, amount, fee, pay_asset)
     }
 
     pub fn approve_burn(env: Env, payout: String) -> Result<(), Error> {
@@ -152,29 +307,79 @@ impl PaymentContract {
     }
 
     /// Get payer address by ID
-    pub fn payer(env: Env, id: String) -> Address {
+    pub fn payer(env: Env, id: String) -> Result<Address, Error> {
         Payer::payer(env, id)
     }
 
     /// Add payer address by ID
-    pub fn add_payer(env: Env, id: String, address: Address) {
+    pub fn add_payer(env: Env, id: String, address: Address) -> Result<(), Error> {
         Payer::add_payer(env, id, address)
     }
 
     /// Remove payer address by ID
-    pub fn remove_payer(env: Env, id: String) {
+    pub fn remove_payer(env: Env, id: String) -> Result<(), Error> {
         Payer::remove_payer(env, id)
     }
 
     pub fn version_build(env: Env) -> String {
         UpgradeableContract::version_build(env)
     }
+
+    /// Return the version of the code
     pub fn version() -> i32 {
         UpgradeableContract::version()
     }
 
-    /// Upgrade smart contract
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        UpgradeableContract::upgrade(env, new_wasm_hash)
+    /// Return the storage schema version of the smart contract
+    pub fn schema_version(env: Env) -> u32 {
+        Schema::version(&env)
+    }
+
+    /// Migrate the storage to the schema version of the code after upgrade,
+    /// other calls fail with SchemaOutdated until the migration is done
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        Schema::migrate(env)
+    }
+
+    /// Extend time to live of the smart contract configuration and the given storage entries
+    pub fn extend_all(env: Env, keys: Vec<StorageKey>) -> Result<(), Error> {
+        Ttl::extend_all(env, keys)
+    }
+
+    /// Extend time to live of all storage entries of the order
+    pub fn bump_order(env: Env, order: String) -> Result<(), Error> {
+        Ttl::bump_order(env, order)
+    }
+
+    /// Get minimal delay between scheduling and execution of the upgrade in seconds
+    pub fn upgrade_delay(env: Env) -> u64 {
+        UpgradeableContract::upgrade_delay(&env)
+    }
+
+    /// Set minimal delay between scheduling and execution of the upgrade in seconds,
+    /// a shorter delay applies after the current one
+    pub fn set_upgrade_delay(env: Env, delay: u64) {
+        UpgradeableContract::set_upgrade_delay(env, delay)
+    }
+
+    /// Get the upgrade scheduled by the admin
+    pub fn pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        UpgradeableContract::pending_upgrade(env)
+    }
+
+    /// Schedule upgrade of smart contract to the 'hash' of installed code,
+    /// the 'eta' ledger timestamp must be at least the upgrade delay ahead
+    pub fn schedule_upgrade(env: Env, hash: BytesN<32>, eta: u64) {
+        UpgradeableContract::schedule_upgrade(env, hash, eta)
+    }
+
+    /// Cancel the scheduled upgrade
+    pub fn cancel_upgrade(env: Env) {
+        UpgradeableContract::cancel_upgrade(env)
+    }
+
+    /// Upgrade smart contract after the scheduled time, call 'migrate' after the upgrade
+    pub fn execute_upgrade(env: Env) {
+        UpgradeableContract::execute_upgrade(env)
     }
 }
\ No newline at end of file
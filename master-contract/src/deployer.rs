@@ -1,69 +1,115 @@
 use soroban_sdk::{Env, String, Address, Val, EnvBase, Bytes};
 use soroban_sdk::unwrap::UnwrapOptimized;
+use crate::config::Config;
+use crate::error::Error;
+use crate::issuer::Issuer;
+use crate::order::Order;
 use crate::serialize_xdr::{CPAsset, CPWriteXdr};
-use crate::store::{get_pay_asset_info, AssetInfo, OrderInfo, StorageKey, ADMIN, LAST_ASSET};
+use crate::strkey::account_id;
+use crate::store::{get_pay_asset, AssetInfo, OrderInfo, StorageKey};
+use crate::ttl::Ttl;
+
+/// Maximum length of the stellar asset code
+const MAX_CODE_LEN: usize = 12;
 
 pub struct Deployer;
 
 impl Deployer {
+    /// Issue a new asset for the 'order' by the 'issuer'.
+    /// If the 'code' is given it is used as the asset code, otherwise the code is derived
+    /// from the pay asset code and the last asset issued by this smart contract.
+    /// The 'issuer' must be the admin or an address added to the issuers allow-list.
+    /// The order is paid out in the 'settlement' pay asset if it is given,
+    /// otherwise in the default pay asset.
+    /// The order can't be paid after the 'payment_deadline' and must be settled
+    /// until the 'delivery_deadline', both are ledger timestamps.
+    /// The existing order is returned as is, unless other deadlines or settlement are given
     pub fn deploy(
         env: Env,
         order: String,
-        issuer: Address
-    ) -> (Address, String, Address) {
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
-
-        // check is order already exists
-        let is_order_exists = env.storage().persistent()
-            .has::<StorageKey>(&StorageKey::Order(order.clone()));
-        if is_order_exists {
-            let order_from_store = env.storage().persistent()
-                .get::<_, OrderInfo>(&StorageKey::Order(order.clone())).unwrap();
-            return (order_from_store.contract, order_from_store.code, order_from_store.issuer);
-        }
-        let pay_asset: OrderInfo = get_pay_asset_info(&env).unwrap();
-
-        let symbols = Bytes::from_slice(
-            &env, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789".as_bytes());
-
-        let last_code: &mut [u8; 12] = &mut [0u8; 12];
-        let prefix_len = pay_asset.code.len() as usize;
-        env.string_copy_to_slice(pay_asset.code.to_object(), Val::U32_ZERO,
-                                 last_code[..prefix_len].as_mut()).unwrap_optimized();
-        let len: usize;
-        let last_asset: String = env.storage().persistent()
-            .get(&LAST_ASSET)
-            .unwrap_or(String::from_str(&env, "A"));
+        issuer: Address,
+        code: Option<String>,
+        settlement: Option<Address>,
+        payment_deadline: Option<u64>,
+        delivery_deadline: Option<u64>,
+    ) -> Result<(Address, String, Address), Error> {
+        Config::require_admin(&env)?;
+        Self::deploy_authorized(
+            env, order, issuer, code, settlement, payment_deadline, delivery_deadline)
+    }
 
-        len = last_asset.len() as usize;
-        env.string_copy_to_slice(
-            last_asset.to_object(), Val::U32_ZERO,
-            last_code[prefix_len..len + prefix_len].as_mut())
-            .unwrap_optimized();
-        let mut current_string = <Bytes>::from_slice(&env,
-                                                     &last_code[..len + prefix_len]);
-        increment_string(&mut current_string, &symbols);
+    /// Issue the asset for the 'order' like 'deploy', the admin signature is already required
+    /// by the caller, an address can be authorized only once in the invocation
+    pub(crate) fn deploy_authorized(
+        env: Env,
+        order: String,
+        issuer: Address,
+        code: Option<String>,
+        settlement: Option<Address>,
+        payment_deadline: Option<u64>,
+        delivery_deadline: Option<u64>,
+    ) -> Result<(Address, String, Address), Error> {
+        Ttl::extend_config(&env);
+
+        // check is order already exists, it can't be deployed again with other terms
+        if let Some(order_from_store) = env.storage().persistent()
+            .get::<_, OrderInfo>(&StorageKey::Order(order.clone())) {
+            let state = Order::load(&env, &order)?;
+            Ttl::extend(&env, &StorageKey::Settlement(order.clone()));
+            let stored_settlement = env.storage().persistent()
+                .get::<_, Address>(&StorageKey::Settlement(order.clone()));
+            if (payment_deadline.is_some() && payment_deadline != state.payment_deadline)
+                || (delivery_deadline.is_some() && delivery_deadline != state.delivery_deadline)
+                || (settlement.is_some() && settlement != stored_settlement) {
+                return Err(Error::AlreadyInitialized);
+            }
+            return Ok((order_from_store.contract, order_from_store.code, order_from_store.issuer));
+        }
+        // only stellar accounts can sign as an issuer of the asset
+        account_id(&issuer)?;
+        if !Issuer::is_allowed(&env, &issuer) {
+            return Err(Error::IssuerNotAllowed);
+        }
+        if let Some(settlement) = settlement.clone() {
+            get_pay_asset(&env, &settlement)?;
+        }
+        let now = env.ledger().timestamp();
+        if matches!(payment_deadline, Some(deadline) if deadline <= now)
+            || matches!(delivery_deadline, Some(deadline) if deadline <= now) {
+            return Err(Error::DeadlinePassed);
+        }
+        if let (Some(payment), Some(delivery)) = (payment_deadline, delivery_deadline) {
+            if delivery < payment {
+                return Err(Error::BadArgs);
+            }
+        }
 
-        env.bytes_copy_to_slice(
-            current_string.to_object(), Val::U32_ZERO,
-            last_code[..len + prefix_len].as_mut())
-            .unwrap_optimized();
+        let (asset_code, len) = match code {
+            Some(code) => {
+                let requested = validate_asset_code(&code)?;
+                if env.storage().persistent()
+                    .has(&StorageKey::Asset(code.clone(), issuer.clone())) {
+                    return Err(Error::AssetAlreadyExists);
+                }
+                requested
+            }
+            None => Self::next_asset_code(&env, &issuer)?,
+        };
 
         // Convert Symbol to String using the function
-        let asset = CPAsset { code: *last_code, issuer: issuer.clone().to_string() };
-        let asset_serialized = asset.to_xdr(&env).unwrap();
-
-        // Deploy the contract using the uploaded Wasm with the given hash.
-        let deployed_address = env
-            .deployer()
-            .with_stellar_asset(asset_serialized.clone())
-            .deploy();
+        let asset = CPAsset { code: asset_code, issuer: issuer.clone().to_string() };
+        let asset_serialized = asset.to_xdr(&env)?;
+
+        // The pay asset issued by this smart contract has the address of the asset
+        // of the same code and issuer, the stellar asset contract can be deployed only once
+        let asset_deployer = env.deployer().with_stellar_asset(asset_serialized);
+        if get_pay_asset(&env, &asset_deployer.deployed_address()).is_ok() {
+            return Err(Error::AssetAlreadyExists);
+        }
+        let deployed_address = asset_deployer.deploy();
 
-        let code_s = core::str::from_utf8(
-            &last_code[..len + prefix_len])
-            .map_err(|_| "Failed to convert &[u8] to &str")
-            .unwrap();
+        let code_s = core::str::from_utf8(&asset_code[..len])
+            .map_err(|_| Error::InvalidAssetCode)?;
 
         let code_symbol = String::from_str(&env, code_s);
         // store order information
@@ -73,14 +119,12 @@ impl Deployer {
             issuer,
         };
         env.storage().persistent().set(&StorageKey::Order(order.clone()), order_key);
-
-        // store last asset used
-        let code_last = core::str::from_utf8(
-            &last_code[prefix_len..prefix_len + len])
-            .map_err(|_| "Failed to convert &[u8] to &str")
-            .unwrap();
-        let code_symbol = String::from_str(&env, code_last);
-        env.storage().persistent().set(&LAST_ASSET, &code_symbol);
+        Ttl::extend(&env, &StorageKey::Order(order.clone()));
+        Order::create(&env, &order, payment_deadline, delivery_deadline);
+        if let Some(settlement) = settlement {
+            env.storage().persistent().set(&StorageKey::Settlement(order.clone()), &settlement);
+            Ttl::extend(&env, &StorageKey::Settlement(order.clone()));
+        }
 
         // store asset information
         let asset_key = &AssetInfo {
@@ -89,9 +133,79 @@ impl Deployer {
 
         env.storage().persistent().set(&StorageKey::Asset(
             order_key.code.clone(), order_key.issuer.clone()), asset_key);
+        Ttl::extend(&env, &StorageKey::Asset(order_key.code.clone(), order_key.issuer.clone()));
+
+        Ok((deployed_address, order_key.code.clone(), order_key.issuer.clone()))
+    }
+
+    /// Derive the next free asset code for the 'issuer' from the pay asset code
+    /// and the last asset issued by this smart contract
+    fn next_asset_code(env: &Env, issuer: &Address) -> Result<([u8; 12], usize), Error> {
+        let mut config = Config::load(env)?;
+        let pay_asset = config.pay_asset.clone();
+
+        let symbols = Bytes::from_slice(
+            env, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789".as_bytes());
+
+        let last_code: &mut [u8; 12] = &mut [0u8; 12];
+        let prefix_len = pay_asset.code.len() as usize;
+        env.string_copy_to_slice(pay_asset.code.to_object(), Val::U32_ZERO,
+                                 last_code[..prefix_len].as_mut()).unwrap_optimized();
+        let last_asset = config
+            .last_asset
+            .clone()
+            .unwrap_or(String::from_str(env, "A"));
+
+        let len = last_asset.len() as usize;
+        if len + prefix_len > MAX_CODE_LEN {
+            return Err(Error::InvalidAssetCode);
+        }
+        env.string_copy_to_slice(
+            last_asset.to_object(), Val::U32_ZERO,
+            last_code[prefix_len..len + prefix_len].as_mut())
+            .unwrap_optimized();
+        let mut current_string = <Bytes>::from_slice(env,
+                                                     &last_code[..len + prefix_len]);
+        // skip codes that were already requested explicitly for the issuer
+        let mut len;
+        loop {
+            increment_string(&mut current_string, &symbols);
+            len = current_string.len() as usize;
+            if len > MAX_CODE_LEN {
+                return Err(Error::InvalidAssetCode);
+            }
+            *last_code = [0u8; 12];
+            current_string.copy_into_slice(last_code[..len].as_mut());
+            let current_code = String::from_bytes(env, &last_code[..len]);
+            if !env.storage().persistent()
+                .has(&StorageKey::Asset(current_code, issuer.clone())) {
+                break;
+            }
+        }
+
+        // store last asset used
+        let code_last = core::str::from_utf8(&last_code[prefix_len..len])
+            .map_err(|_| Error::InvalidAssetCode)?;
+        config.last_asset = Some(String::from_str(env, code_last));
+        config.save(env);
 
-        (deployed_address, order_key.code.clone(), order_key.issuer.clone())
+        Ok((*last_code, len))
+    }
+}
+
+/// Validate the asset code within stellar rules of asset names convention,
+/// the code must have from 1 to 12 alphanumeric symbols
+pub(crate) fn validate_asset_code(code: &String) -> Result<([u8; 12], usize), Error> {
+    let len = code.len() as usize;
+    if !(1..=MAX_CODE_LEN).contains(&len) {
+        return Err(Error::InvalidAssetCode);
+    }
+    let mut asset_code = [0u8; 12];
+    code.copy_into_slice(&mut asset_code[..len]);
+    if !asset_code[..len].iter().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidAssetCode);
     }
+    Ok((asset_code, len))
 }
 
 fn increment_string(s: &mut Bytes, symbols: &Bytes) {
@@ -15,4 +15,23 @@ pub enum Error {
     NotInitialized = 9,
     UnknownSigner = 10,
     IncorrectTransfer = 11,
+    InvalidAssetCode = 12,
+    AssetAlreadyExists = 13,
+    IssuerNotAllowed = 14,
+    InvalidXdr = 15,
+    InvalidAddress = 16,
+    UnknownPayAsset = 17,
+    RateNotFound = 18,
+    StaleRate = 19,
+    OrderNotFound = 20,
+    OrderClosed = 21,
+    InvalidOrderStatus = 22,
+    DeadlinePassed = 23,
+    OrderNotExpired = 24,
+    SchemaOutdated = 25,
+    AssetNotFound = 28,
+    PayerNotFound = 29,
+    WithdrawNotFound = 30,
+    PayAssetMissing = 31,
+    FutureRate = 32,
 }
\ No newline at end of file
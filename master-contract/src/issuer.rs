@@ -0,0 +1,48 @@
+use soroban_sdk::{Address, Env};
+use crate::config::Config;
+use crate::error::Error;
+use crate::schema::Schema;
+use crate::store::StorageKey;
+use crate::ttl::Ttl;
+
+pub struct Issuer;
+
+impl Issuer {
+    /// Return true if the address is allowed to issue order assets.
+    /// The admin address is always allowed, it issues assets for orders created by the mint
+    pub fn is_allowed(env: &Env, issuer: &Address) -> bool {
+        if let Ok(config) = Config::load(env) {
+            if config.admin == *issuer {
+                return true;
+            }
+        };
+        Ttl::extend(env, &StorageKey::Issuer(issuer.clone()));
+        env.storage()
+            .persistent()
+            .get::<_, bool>(&StorageKey::Issuer(issuer.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Add issuer to the allow-list.
+    pub fn add_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        Schema::require_admin(&env)?;
+        env.storage().persistent().set(&StorageKey::Issuer(issuer.clone()), &true);
+        Ttl::extend(&env, &StorageKey::Issuer(issuer));
+        Ok(())
+    }
+
+    /// Remove issuer from the allow-list.
+    pub fn remove_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        Schema::require_admin(&env)?;
+        let is_exist = env.storage()
+            .persistent()
+            .has(&StorageKey::Issuer(issuer.clone()));
+
+        if is_exist {
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::Issuer(issuer));
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -4,7 +4,8 @@ extern crate alloc;
 mod deployer;
 mod error;
 mod minting;
-mod serialize_xdr;
+pub mod serialize_xdr;
+mod strkey;
 mod store;
 mod upgrade;
 mod contract;
@@ -15,4 +16,18 @@ mod transfer;
 mod burn;
 mod commission;
 mod order;
+mod issuer;
+mod payout;
+mod pay_asset;
+mod rate;
+mod batch;
+mod ttl;
+mod config;
+mod schema;
 
+pub use crate::contract::{PaymentContract, PaymentContractClient};
+pub use crate::error::Error;
+pub use crate::store::StorageKey;
+pub use crate::order::{Order, OrderStatus, MAX_REFUNDS};
+pub use crate::store::{MintRequest, PayoutInfo, RateInfo, TransferRequest};
+pub use marketplace_asset::upgrade::MIN_UPGRADE_DELAY;
@@ -1,19 +1,15 @@
 use crate::deployer::Deployer;
+use crate::config::Config;
 use crate::error::Error;
+use crate::order::Order;
 use crate::payer::Payer;
 use crate::store::StorageKey::Payments;
-use crate::store::{OrderInfo, PaymentInfo, StorageKey, ADMIN};
-use soroban_sdk::{contractclient, vec, Address, Env, String, Vec};
+use crate::store::{get_order_info, get_settlement_asset_info, PaymentInfo, StorageKey};
+use crate::ttl::Ttl;
+use contract_types::mint::MintClient;
+use soroban_sdk::{vec, Address, Env, String, Vec};
 use crate::commission::Commission;
 
-#[contractclient(name = "MintClient")]
-trait MintInterface {
-    fn mint(env: Env, to: Address, amount: i128);
-    fn set_admin(env: Env, new_admin: Address);
-    fn clawback(env: Env, from: Address, amount: i128);
-    fn set_authorized(env: Env, id: &Address, authorize: &bool);
-}
-
 pub struct Minter;
 
 impl Minter {
@@ -21,6 +17,7 @@ impl Minter {
     /// If the order wasn't registered before by the 'deploy' function, it will be created, and
     /// the admin address will be assigned as an issuer
     /// the function will issue the 'amount' assets associated with the 'order'
+    /// the 'settlement' pay asset is assigned to the order only when it is created
     pub fn mint(
         env: Env,
         order: String,
@@ -28,81 +25,74 @@ impl Minter {
         payer: String,
         amount: i128,
         fee: i128,
+        settlement: Option<Address>,
+    ) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+        Self::mint_authorized(env, order, payment, payer, amount, fee, settlement)
+    }
+
+    /// Mint the asset for the paid order like 'mint', the admin signature is already required
+    /// by the caller, an address can be authorized only once in the invocation
+    pub(crate) fn mint_authorized(
+        env: Env,
+        order: String,
+        payment: String,
+        payer: String,
+        amount: i128,
+        fee: i128,
+        settlement: Option<Address>,
     ) -> Result<(), Error> {
         // Verify the amount is positive after commission deduction
         if amount - fee <= 0 {
             return Err(Error::NegativeAmount);
         }
-        
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
+        let admin = Config::admin(&env)?;
+        Ttl::extend_config(&env);
 
         // check if the order doesn't exist
-        if env
+        if !env
             .storage()
             .persistent()
             .has(&StorageKey::Order(order.clone()))
-            == false
         {
             // create order
-            Deployer::deploy(env.clone(), order.clone(), admin.clone());
+            Deployer::deploy_authorized(
+                env.clone(), order.clone(), admin.clone(), None, settlement, None, None)?;
         }
         // Get order info
-        let order_info: OrderInfo = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Order(order.clone()))
-            .unwrap();
+        let order_info = get_order_info(&env, &order)?;
+
+        let mut state = Order::load(&env, &order)?;
+        state.on_paid(amount, fee, env.ledger().timestamp())?;
+        state.save(&env);
 
         let date = Option::from(env.ledger().timestamp());
 
         // Update information about payment operations
-        if env.storage().persistent().has(&Payments(
-            order_info.code.clone(),
-            order_info.issuer.clone(),
-        )) {
-            let mut recorded_payments: Vec<PaymentInfo> = env
-                .storage()
-                .persistent()
-                .get(&Payments(
-                    order_info.code.clone(),
-                    order_info.issuer.clone(),
-                ))
-                .unwrap();
-            recorded_payments.push_back(PaymentInfo {
-                payer: payer.clone(),
-                payment,
-                amount,
-                fee,
-                date,
-            });
-            env.storage().persistent().set(
-                &Payments(order_info.code.clone(), order_info.issuer.clone()),
-                &recorded_payments,
-            );
-        } else {
-            let create_payment = vec![
-                &env,
-                PaymentInfo {
-                    payer: payer.clone(),
-                    payment,
-                    amount,
-                    fee,
-                    date,
-                },
-            ];
-            env.storage().persistent().set(
-                &Payments(order_info.code.clone(), order_info.issuer.clone()),
-                &create_payment,
-            );
-        }
+        let payments_key = Payments(order_info.code.clone(), order_info.issuer.clone());
+        Ttl::extend(&env, &payments_key);
+        let mut recorded_payments: Vec<PaymentInfo> = env
+            .storage()
+            .persistent()
+            .get(&payments_key)
+            .unwrap_or(vec![&env]);
+        recorded_payments.push_back(PaymentInfo {
+            payer: payer.clone(),
+            payment,
+            amount,
+            fee,
+            date,
+        });
+        env.storage().persistent().set(&payments_key, &recorded_payments);
+        Ttl::extend(&env, &payments_key);
 
         // Get address for payer
-        let to = Payer::payer(env.clone(), payer);
+        let to = Payer::payer(env.clone(), payer)?;
         // Perform the mint.
         let client = MintClient::new(&env, &order_info.contract);
         client.mint(&to, &(amount - fee));
-        let _ = Commission::pay_commission(env.clone(), &fee);
+        let pay_asset = get_settlement_asset_info(&env, &order)?;
+        let _ = Commission::pay_commission(env.clone(), &pay_asset, &fee);
         Ok(())
     }
 }
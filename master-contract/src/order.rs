@@ -0,0 +1,366 @@
+use soroban_sdk::{contracttype, token, vec, Address, Env, String, Vec};
+use crate::config::Config;
+use crate::error::Error;
+use contract_types::mint::MintClient;
+use crate::payer::Payer;
+use crate::payout::Payout;
+use crate::rate::Rate;
+use crate::ttl::Ttl;
+use crate::store::StorageKey::{Payments, RefundCursor, Refunds, Transfers};
+use crate::store::{
+    get_order_info, get_settlement_asset_info, OrderInfo, PaymentInfo, RateInfo, StorageKey,
+    TransferInfo
+};
+
+/// Maximum number of pending transfers and payments refunded by one call
+pub const MAX_REFUNDS: u32 = 20;
+
+#[contracttype]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OrderStatus {
+    /// Order asset is issued, but not paid yet
+    Created = 1,
+    /// Order is paid and order asset is minted to payers
+    Funded = 2,
+    /// Order asset is transferred to beneficiaries and waits for approval
+    InTransfer = 3,
+    /// All transfers are approved and paid out
+    Settled = 4,
+    /// Order is closed by the admin before any payment
+    Cancelled = 5,
+    /// Order is closed by the admin and outstanding balances are refunded
+    Refunded = 6,
+    /// Order is closed after the deadline, outstanding balances are refunded by the admin
+    Expired = 7,
+}
+
+/// Order is a lifecycle state of the order, all amounts are in the order asset units
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Order {
+    pub order: String,
+    pub status: OrderStatus,
+    /// total amount of payments including fees
+    pub paid: i128,
+    /// total amount of order asset transferred to beneficiaries, pending and approved
+    pub transferred: i128,
+    /// total amount of approved transfers including fees
+    pub paid_out: i128,
+    /// total amount refunded to payers
+    pub refunded: i128,
+    /// total amount of fees of payments and approved transfers
+    pub fees: i128,
+    pub buyer: Option<String>,
+    pub seller: Option<String>,
+    pub demand: Option<Address>,
+    pub goods: Option<Address>,
+    /// timestamp after which the order can't be paid or transferred
+    pub payment_deadline: Option<u64>,
+    /// timestamp until which all transfers must be settled
+    pub delivery_deadline: Option<u64>,
+}
+
+impl Order {
+    /// Create the state for the new order with optional payment and delivery deadlines
+    pub fn create(
+        env: &Env,
+        order: &String,
+        payment_deadline: Option<u64>,
+        delivery_deadline: Option<u64>,
+    ) {
+        Self::new(order, payment_deadline, delivery_deadline).save(env);
+    }
+
+    fn new(order: &String, payment_deadline: Option<u64>, delivery_deadline: Option<u64>) -> Order {
+        Order {
+            order: order.clone(),
+            status: OrderStatus::Created,
+            paid: 0,
+            transferred: 0,
+            paid_out: 0,
+            refunded: 0,
+            fees: 0,
+            buyer: None,
+            seller: None,
+            demand: None,
+            goods: None,
+            payment_deadline,
+            delivery_deadline,
+        }
+    }
+
+    /// Load the state of the order, orders issued before the state was introduced
+    /// start from the created state, it is stored by the first call that changes it
+    pub fn load(env: &Env, order: &String) -> Result<Order, Error> {
+        if let Some(state) = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::OrderState(order.clone()))
+        {
+            Ttl::extend(env, &StorageKey::OrderState(order.clone()));
+            return Ok(state);
+        }
+        if !env.storage().persistent().has(&StorageKey::Order(order.clone())) {
+            return Err(Error::OrderNotFound);
+        }
+        Ok(Self::new(order, None, None))
+    }
+
+    pub fn save(&self, env: &Env) {
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OrderState(self.order.clone()), self);
+        Ttl::extend(env, &StorageKey::OrderState(self.order.clone()));
+    }
+
+    /// Return true if the order doesn't accept payments and transfers anymore
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self.status,
+            OrderStatus::Cancelled | OrderStatus::Refunded | OrderStatus::Expired
+        )
+    }
+
+    /// Return true if the payment deadline is passed at 'now'
+    pub fn is_payment_overdue(&self, now: u64) -> bool {
+        matches!(self.payment_deadline, Some(deadline) if now > deadline)
+    }
+
+    /// Return true if the delivery deadline is passed at 'now'
+    pub fn is_delivery_overdue(&self, now: u64) -> bool {
+        matches!(self.delivery_deadline, Some(deadline) if now > deadline)
+    }
+
+    /// Amount of transfers waiting for approval
+    pub fn pending(&self) -> i128 {
+        self.transferred - self.paid_out
+    }
+
+    /// Record the payment of 'amount' with 'fee' made at 'now'
+    pub fn on_paid(&mut self, amount: i128, fee: i128, now: u64) -> Result<(), Error> {
+        if self.is_closed() {
+            return Err(Error::OrderClosed);
+        }
+        if self.is_payment_overdue(now) {
+            return Err(Error::DeadlinePassed);
+        }
+        self.paid += amount;
+        self.fees += fee;
+        if self.pending() == 0 {
+            self.status = OrderStatus::Funded;
+        }
+        Ok(())
+    }
+
+    /// Record the transfer of 'amount' made at 'now' waiting for approval
+    pub fn on_transfer(&mut self, amount: i128, now: u64) -> Result<(), Error> {
+        if self.is_closed() {
+            return Err(Error::OrderClosed);
+        }
+        if self.is_payment_overdue(now) {
+            return Err(Error::DeadlinePassed);
+        }
+        self.transferred += amount;
+        self.status = OrderStatus::InTransfer;
+        Ok(())
+    }
+
+    /// Record the approved transfer of 'amount' with 'fee'
+    pub fn on_transfer_approved(&mut self, amount: i128, fee: i128) {
+        self.paid_out += amount;
+        self.fees += fee;
+        self.refresh_transfer_status();
+    }
+
+    /// Record the rejected transfer of 'amount' returned to the payer
+    pub fn on_transfer_rejected(&mut self, amount: i128) {
+        self.transferred -= amount;
+        self.refresh_transfer_status();
+    }
+
+    fn refresh_transfer_status(&mut self) {
+        if self.is_closed() {
+            return;
+        }
+        self.status = if self.pending() > 0 {
+            OrderStatus::InTransfer
+        } else if self.paid_out > 0 {
+            OrderStatus::Settled
+        } else {
+            OrderStatus::Funded
+        };
+    }
+
+    /// Return the state of the order
+    pub fn order_summary(env: Env, order: String) -> Result<Order, Error> {
+        Self::load(&env, &order)
+    }
+
+    /// Link the order to the buyer and seller ids and to the demand and goods smart contracts
+    pub fn link_order(
+        env: Env,
+        order: String,
+        buyer: Option<String>,
+        seller: Option<String>,
+        demand: Option<Address>,
+        goods: Option<Address>,
+    ) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+
+        let mut state = Self::load(&env, &order)?;
+        state.buyer = buyer;
+        state.seller = seller;
+        state.demand = demand;
+        state.goods = goods;
+        state.save(&env);
+        Ok(())
+    }
+
+    /// Cancel the order, pending transfers are rejected and outstanding order asset balances
+    /// are clawed back from payers and refunded in the settlement pay asset.
+    /// The cancelled order can't be paid or transferred anymore.
+    /// At most MAX_REFUNDS transfers and payments are refunded, the number of remaining ones
+    /// is returned and they are refunded by 'refund_order'
+    pub fn cancel_order(env: Env, order: String) -> Result<u32, Error> {
+        Config::require_admin(&env)?;
+
+        let mut state = Self::load(&env, &order)?;
+        if state.is_closed() {
+            return Err(Error::OrderClosed);
+        }
+        let remaining = Self::refund(&env, &mut state, MAX_REFUNDS)?;
+        state.status = if state.paid == 0 {
+            OrderStatus::Cancelled
+        } else {
+            OrderStatus::Refunded
+        };
+        state.save(&env);
+        Ok(remaining)
+    }
+
+    /// Expire the order after its deadline, anyone can call it.
+    /// The order expires if it isn't paid until the payment deadline,
+    /// or isn't settled until the delivery deadline. Pending transfers and outstanding
+    /// order asset balances are refunded later by the admin with 'refund_order':
+    /// the order asset is clawed back and a minted pay asset is issued with the signature
+    /// of the admin, which the caller of the expiry doesn't have
+    pub fn expire_order(env: Env, order: String) -> Result<(), Error> {
+        let mut state = Self::load(&env, &order)?;
+        if state.is_closed() {
+            return Err(Error::OrderClosed);
+        }
+        let now = env.ledger().timestamp();
+        let is_unpaid = state.status == OrderStatus::Created;
+        let is_expired = (is_unpaid && state.is_payment_overdue(now))
+            || (state.status != OrderStatus::Settled && state.is_delivery_overdue(now));
+        if !is_expired {
+            return Err(Error::OrderNotExpired);
+        }
+        state.status = OrderStatus::Expired;
+        state.save(&env);
+        Ok(())
+    }
+
+    /// Refund pending transfers and outstanding order asset balances of the closed order,
+    /// the order asset is clawed back and the settlement pay asset is paid out by the admin.
+    /// At most 'limit' transfers and payments, but not more than MAX_REFUNDS, are refunded
+    /// by one call, the number of remaining ones is returned
+    pub fn refund_order(env: Env, order: String, limit: u32) -> Result<u32, Error> {
+        Config::require_admin(&env)?;
+
+        let mut state = Self::load(&env, &order)?;
+        if !state.is_closed() {
+            return Err(Error::InvalidOrderStatus);
+        }
+        let remaining = Self::refund(&env, &mut state, limit.min(MAX_REFUNDS))?;
+        state.save(&env);
+        Ok(remaining)
+    }
+
+    /// Refund up to 'limit' pending transfers and outstanding order asset balances of payers
+    /// in the settlement pay asset of the order, return the number of remaining ones.
+    /// Refunded transfers are removed from the pending list, payments are walked
+    /// from the stored cursor
+    fn refund(env: &Env, state: &mut Order, limit: u32) -> Result<u32, Error> {
+        let order_info = get_order_info(env, &state.order)?;
+        let pay_asset = get_settlement_asset_info(env, &state.order)?;
+        let rate = Rate::order_rate(env, &state.order, &pay_asset.code)?;
+
+        // Reject pending transfers, the order asset is already clawed back from the payer
+        let transfers_key = Transfers(order_info.code.clone(), order_info.issuer.clone());
+        let mut pending_transfers: Vec<TransferInfo> = env
+            .storage()
+            .persistent()
+            .get(&transfers_key)
+            .unwrap_or(vec![env]);
+        let mut processed = 0;
+        while processed < limit {
+            let Some(pending_transfer) = pending_transfers.pop_front() else {
+                break;
+            };
+            state.on_transfer_rejected(pending_transfer.amount);
+            Self::refund_payer(env, state, &pay_asset, &rate, pending_transfer.payer, pending_transfer.amount)?;
+            processed += 1;
+        }
+        env.storage().persistent().set(&transfers_key, &pending_transfers);
+
+        // Claw back outstanding order asset balances from payers, the balance of the payer
+        // with several payments is refunded at the first one
+        let payments: Vec<PaymentInfo> = env
+            .storage()
+            .persistent()
+            .get(&Payments(order_info.code.clone(), order_info.issuer.clone()))
+            .unwrap_or(vec![env]);
+        let cursor_key = RefundCursor(state.order.clone());
+        let mut cursor: u32 = env.storage().persistent().get(&cursor_key).unwrap_or(0);
+        let order_client = MintClient::new(env, &order_info.contract);
+        let balance_client = token::Client::new(env, &order_info.contract);
+        while processed < limit && cursor < payments.len() {
+            let payer = payments.get_unchecked(cursor).payer;
+            let payer_address = Payer::payer(env.clone(), payer.clone())?;
+            let balance = balance_client.balance(&payer_address);
+            if balance > 0 {
+                order_client.clawback(&payer_address, &balance);
+                Self::refund_payer(env, state, &pay_asset, &rate, payer, balance)?;
+            }
+            cursor += 1;
+            processed += 1;
+        }
+        env.storage().persistent().set(&cursor_key, &cursor);
+        Ttl::extend(env, &cursor_key);
+        Ok(pending_transfers.len() + payments.len() - cursor)
+    }
+
+    fn refund_payer(
+        env: &Env,
+        state: &mut Order,
+        pay_asset: &OrderInfo,
+        rate: &Option<RateInfo>,
+        payer: String,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let refund_amount = Rate::convert(rate, amount)?;
+        let payer_address = Payer::payer(env.clone(), payer.clone())?;
+        Payout::pay(env, pay_asset, &payer_address, &refund_amount);
+        state.refunded += amount;
+
+        let order_info = get_order_info(env, &state.order)?;
+        let refunds_key = Refunds(order_info.code, order_info.issuer);
+        let mut refunds: Vec<TransferInfo> = env
+            .storage()
+            .persistent()
+            .get(&refunds_key)
+            .unwrap_or(vec![env]);
+        refunds.push_back(TransferInfo {
+            transfer: state.order.clone(),
+            payer: payer.clone(),
+            beneficiary: payer,
+            amount,
+            fee: 0,
+            date: Option::from(env.ledger().timestamp()),
+        });
+        env.storage().persistent().set(&refunds_key, &refunds);
+        Ttl::extend(env, &refunds_key);
+        Ok(())
+    }
+}
\ No newline at end of file
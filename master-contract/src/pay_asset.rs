@@ -0,0 +1,132 @@
+use soroban_sdk::{token, vec, Address, Bytes, Env, String, Vec};
+use crate::config::Config;
+use crate::deployer::validate_asset_code;
+use crate::error::Error;
+use crate::serialize_xdr::{CPAsset, CPWriteXdr};
+use crate::store::{OrderInfo, PayMode, StorageKey};
+use crate::strkey::account_id;
+use crate::ttl::Ttl;
+
+/// Length of the strkey of a stellar account
+const ACCOUNT_STRKEY_LEN: usize = 56;
+
+pub struct PayAsset;
+
+impl PayAsset {
+    /// Deploy a new stellar asset contract for the pay asset issued by the admin,
+    /// the code has from 1 to 12 alphanumeric symbols
+    pub fn issue(e: &Env, admin: &Address, pay_asset: &String) -> Result<(OrderInfo, PayMode), Error> {
+        let (asset_code, _) = validate_asset_code(pay_asset)?;
+        // admin is an issuer of the pay asset and must be a stellar account
+        account_id(admin)?;
+        let asset = CPAsset { code: asset_code, issuer: admin.to_string() };
+        let asset_serialized = asset.to_xdr(e)?;
+
+        // Deploy the contract using the uploaded Wasm with given hash.
+        let deployed_address = e
+            .deployer()
+            .with_stellar_asset(asset_serialized.clone())
+            .deploy();
+        // store cash out information
+        Ok((OrderInfo {
+            contract: deployed_address,
+            code: pay_asset.clone(),
+            issuer: admin.clone(),
+        }, PayMode::Mint))
+    }
+
+    /// Bind the existing stellar asset contract as the pay asset,
+    /// the payouts are transferred from the smart contract treasury
+    pub fn bind(e: &Env, pay_asset: &String, pay_contract: Address) -> Result<(OrderInfo, PayMode), Error> {
+        if token::Client::new(e, &pay_contract).symbol() != *pay_asset {
+            return Err(Error::BadArgs);
+        }
+        let issuer = Self::issuer(e, pay_asset, &pay_contract)?;
+        Ok((OrderInfo {
+            contract: pay_contract,
+            code: pay_asset.clone(),
+            issuer,
+        }, PayMode::Treasury))
+    }
+
+    /// Return the issuer of the stellar asset contract, the contract name is 'code:issuer'
+    fn issuer(e: &Env, pay_asset: &String, pay_contract: &Address) -> Result<Address, Error> {
+        let name = token::Client::new(e, pay_contract).name();
+        let len = name.len() as usize;
+        let code_len = pay_asset.len() as usize;
+        if len != code_len + 1 + ACCOUNT_STRKEY_LEN {
+            return Err(Error::BadArgs);
+        }
+        let mut buffer = [0u8; 12 + 1 + ACCOUNT_STRKEY_LEN];
+        name.copy_into_slice(&mut buffer[..len]);
+        let issuer = Bytes::from_slice(e, &buffer[code_len + 1..len]);
+        Ok(Address::from_string_bytes(&issuer))
+    }
+
+    /// Return contracts of all pay assets, the default pay asset goes first
+    pub fn pay_assets(env: Env) -> Result<Vec<Address>, Error> {
+        let config = Config::load(&env)?;
+        let mut contracts = vec![&env, config.pay_asset.contract];
+        for pay_asset in config.pay_assets.iter() {
+            contracts.push_back(pay_asset.contract);
+        }
+        Ok(contracts)
+    }
+
+    /// Return true if the pay asset with the 'code' and the 'issuer' is the default one or added
+    fn is_added(config: &Config, code: &String, issuer: &Address) -> bool {
+        let is_same = |pay_asset: &OrderInfo| pay_asset.code == *code && pay_asset.issuer == *issuer;
+        is_same(&config.pay_asset) || config.pay_assets.iter().any(|pay_asset| is_same(&pay_asset))
+    }
+
+    /// Add a pay asset that can be chosen as a settlement asset of the order.
+    /// A new asset is issued by the admin if 'pay_contract' isn't given,
+    /// otherwise the existing asset contract is bound to pay out from the treasury.
+    /// Pay assets are told apart by the code and the issuer
+    pub fn add_pay_asset(
+        env: Env,
+        code: String,
+        pay_contract: Option<Address>,
+    ) -> Result<Address, Error> {
+        let mut config = Config::require_admin(&env)?;
+
+        // an asset issued twice has the same contract, so the issuer is checked before
+        if pay_contract.is_none() && Self::is_added(&config, &code, &config.admin) {
+            return Err(Error::AssetAlreadyExists);
+        }
+        let (pay_asset, pay_mode) = match pay_contract {
+            Some(pay_contract) => Self::bind(&env, &code, pay_contract)?,
+            None => Self::issue(&env, &config.admin, &code)?,
+        };
+        if Self::is_added(&config, &pay_asset.code, &pay_asset.issuer) {
+            return Err(Error::AssetAlreadyExists);
+        }
+        let pay_mode_key = StorageKey::PayMode(pay_asset.code.clone(), pay_asset.issuer.clone());
+        env.storage().persistent().set(&pay_mode_key, &pay_mode);
+        Ttl::extend(&env, &pay_mode_key);
+
+        config.pay_assets.push_back(pay_asset.clone());
+        config.save(&env);
+
+        Ok(pay_asset.contract)
+    }
+
+    /// Remove a pay asset, orders already settled in this asset keep the reference
+    /// and can't be paid out until the asset is added again
+    pub fn remove_pay_asset(env: Env, pay_contract: Address) -> Result<(), Error> {
+        let mut config = Config::require_admin(&env)?;
+
+        let index = config
+            .pay_assets
+            .iter()
+            .position(|pay_asset| pay_asset.contract == pay_contract)
+            .ok_or(Error::UnknownPayAsset)?;
+        let pay_asset = config.pay_assets.get_unchecked(index as u32);
+        config.pay_assets.remove(index as u32);
+        config.save(&env);
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PayMode(pay_asset.code, pay_asset.issuer));
+        Ok(())
+    }
+}
@@ -1,35 +1,31 @@
 use soroban_sdk::{Address, Env, String};
-use crate::store::{StorageKey, ADMIN};
+use crate::error::Error;
+use crate::schema::Schema;
+use crate::store::StorageKey;
+use crate::ttl::Ttl;
 
 pub struct Payer;
 
 impl Payer {
     /// Return the payer address.
-    pub fn payer(env: Env, id: String) -> Address {
-        env.storage().persistent().get(&StorageKey::Payer(id.clone())).unwrap()
+    pub fn payer(env: Env, id: String) -> Result<Address, Error> {
+        Ttl::extend(&env, &StorageKey::Payer(id.clone()));
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Payer(id))
+            .ok_or(Error::PayerNotFound)
     }
 
     /// Add payer.
-    pub fn add_payer(env: Env, id: String, address: Address) {
-        if let Some(admin) = env
-            .storage()
-            .persistent()
-            .get::<_, Address>(&ADMIN)
-        {
-            admin.require_auth();
-        };
+    pub fn add_payer(env: Env, id: String, address: Address) -> Result<(), Error> {
+        Schema::require_admin(&env)?;
         env.storage().persistent().set(&StorageKey::Payer(id.clone()), &address);
+        Ok(())
     }
 
     /// Remove payer.
-    pub fn remove_payer(env: Env, id: String) {
-        if let Some(admin) = env
-            .storage()
-            .persistent()
-            .get::<_, Address>(&ADMIN)
-        {
-            admin.require_auth();
-        };
+    pub fn remove_payer(env: Env, id: String) -> Result<(), Error> {
+        Schema::require_admin(&env)?;
         let is_exist = env.storage()
             .persistent()
             .has(&StorageKey::Payer(id.clone()));
@@ -39,5 +35,6 @@ impl Payer {
                 .persistent()
                 .remove(&StorageKey::Payer(id.clone()));
         }
+        Ok(())
     }
 }
\ No newline at end of file
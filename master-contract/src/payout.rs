@@ -0,0 +1,45 @@
+use soroban_sdk::{token, Address, Env};
+use contract_types::mint::MintClient;
+use crate::store::{get_pay_mode, OrderInfo, PayMode};
+
+pub struct Payout;
+
+impl Payout {
+    /// Pay the 'amount' of the pay asset to the 'to' address.
+    /// The pay asset is minted if it is issued by the admin,
+    /// otherwise it is transferred from the smart contract treasury
+    pub fn pay(env: &Env, pay_asset: &OrderInfo, to: &Address, amount: &i128) {
+        match get_pay_mode(env, pay_asset) {
+            PayMode::Mint => {
+                let client = MintClient::new(env, &pay_asset.contract);
+                client.mint(to, amount);
+            }
+            PayMode::Treasury => {
+                let client = token::Client::new(env, &pay_asset.contract);
+                client.transfer(&env.current_contract_address(), to, amount);
+            }
+        }
+    }
+
+    /// Collect the 'amount' of the pay asset from the 'from' address.
+    /// The pay asset is clawed back if it is issued by the admin,
+    /// otherwise it is transferred to the smart contract treasury and requires 'from' signature
+    pub fn collect(env: &Env, pay_asset: &OrderInfo, from: &Address, amount: &i128) {
+        match get_pay_mode(env, pay_asset) {
+            PayMode::Mint => {
+                let client = MintClient::new(env, &pay_asset.contract);
+                client.clawback(from, amount);
+            }
+            PayMode::Treasury => {
+                let client = token::Client::new(env, &pay_asset.contract);
+                client.transfer(from, &env.current_contract_address(), amount);
+            }
+        }
+    }
+
+    /// Return the pay asset balance of the smart contract treasury
+    pub fn treasury_balance(env: &Env, pay_asset: &OrderInfo) -> i128 {
+        let client = token::Client::new(env, &pay_asset.contract);
+        client.balance(&env.current_contract_address())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use contract_types::oracle::PriceFeedClient;
+use soroban_sdk::{Address, Env, String};
+use crate::config::Config;
+use crate::error::Error;
+use crate::store::{RateInfo, StorageKey};
+use crate::ttl::Ttl;
+
+/// Rates are fixed point numbers with 7 decimals, the same precision as stellar assets
+pub(crate) const RATE_SCALE: i128 = 10_000_000;
+
+/// Default maximum age of the rate in seconds
+const DEFAULT_MAX_RATE_AGE: u64 = 3600;
+
+pub struct Rate;
+
+impl Rate {
+    /// Return the rate of 'base' in 'quote', the price feed oracle is used if it is set,
+    /// otherwise the rate table set by the admin. The rate must not be older than max rate age
+    /// and can't be dated after the current ledger timestamp
+    pub fn rate(env: Env, base: String, quote: String) -> Result<RateInfo, Error> {
+        let config = Config::load(&env)?;
+        let rate_info = match config.oracle {
+            Some(oracle) => {
+                let (rate, date) = PriceFeedClient::new(&env, &oracle).rate(&base, &quote);
+                RateInfo { base, quote, rate, date }
+            }
+            None => {
+                let storage_key = StorageKey::Rate(base, quote);
+                Ttl::extend(&env, &storage_key);
+                env.storage()
+                    .persistent()
+                    .get(&storage_key)
+                    .ok_or(Error::RateNotFound)?
+            }
+        };
+        if rate_info.rate <= 0 {
+            return Err(Error::RateNotFound);
+        }
+        let now = env.ledger().timestamp();
+        if rate_info.date > now {
+            return Err(Error::FutureRate);
+        }
+        let max_age = config.max_rate_age.unwrap_or(DEFAULT_MAX_RATE_AGE);
+        if now - rate_info.date > max_age {
+            return Err(Error::StaleRate);
+        }
+        Ok(rate_info)
+    }
+
+    /// Set the rate of 'base' in 'quote' scaled by 10^7 with the current ledger timestamp.
+    pub fn set_rate(env: Env, base: String, quote: String, rate: i128) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+        if rate <= 0 {
+            return Err(Error::BadArgs);
+        }
+        let rate_info = RateInfo {
+            base: base.clone(),
+            quote: quote.clone(),
+            rate,
+            date: env.ledger().timestamp(),
+        };
+        let storage_key = StorageKey::Rate(base, quote);
+        env.storage().persistent().set(&storage_key, &rate_info);
+        Ttl::extend(&env, &storage_key);
+        Ok(())
+    }
+
+    /// Set the price feed oracle, the rate table is used if the oracle is removed.
+    pub fn set_oracle(env: Env, oracle: Option<Address>) -> Result<(), Error> {
+        let mut config = Config::require_admin(&env)?;
+        config.oracle = oracle;
+        config.save(&env);
+        Ok(())
+    }
+
+    /// Set the maximum age of the rate in seconds.
+    pub fn set_max_rate_age(env: Env, max_age: u64) -> Result<(), Error> {
+        let mut config = Config::require_admin(&env)?;
+        config.max_rate_age = Some(max_age);
+        config.save(&env);
+        Ok(())
+    }
+
+    /// Set the currency the order asset is denominated in.
+    pub fn set_denomination(env: Env, order: String, currency: String) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+        let storage_key = StorageKey::Denomination(order);
+        env.storage().persistent().set(&storage_key, &currency);
+        Ttl::extend(&env, &storage_key);
+        Ok(())
+    }
+
+    /// Return the rate to pay out the 'order' in the 'pay_asset' code,
+    /// none is returned if the order is denominated in the pay asset and paid out 1:1
+    pub fn order_rate(env: &Env, order: &String, pay_asset: &String) -> Result<Option<RateInfo>, Error> {
+        Ttl::extend(env, &StorageKey::Denomination(order.clone()));
+        match env
+            .storage()
+            .persistent()
+            .get::<_, String>(&StorageKey::Denomination(order.clone()))
+        {
+            Some(currency) if currency != *pay_asset => {
+                Ok(Some(Self::rate(env.clone(), currency, pay_asset.clone())?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Convert the order asset 'amount' into the pay asset amount with the 'rate'
+    pub fn convert(rate: &Option<RateInfo>, amount: i128) -> Result<i128, Error> {
+        match rate {
+            Some(rate) => amount
+                .checked_mul(rate.rate)
+                .map(|value| value / RATE_SCALE)
+                .ok_or(Error::BadArgs),
+            None => Ok(amount),
+        }
+    }
+}
\ No newline at end of file
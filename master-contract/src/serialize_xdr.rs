@@ -1,13 +1,31 @@
-use alloc::string::{String};
-use soroban_sdk::{Bytes, Env, Error, IntoVal, String as SdkString};
-use soroban_sdk::xdr::{PublicKey, ToXdr, Uint256};
-use base32;
+use soroban_sdk::{Bytes, Env, IntoVal, String as SdkString};
+use crate::error::Error;
+use crate::strkey::StrKey;
+
+/// Discriminant of the Asset union for the asset code of 1 to 4 symbols
+const ASSET_TYPE_CREDIT_ALPHANUM4: u32 = 1;
+
+/// Discriminant of the Asset union for the asset code of 5 to 12 symbols
+const ASSET_TYPE_CREDIT_ALPHANUM12: u32 = 2;
+
+/// Discriminant of the PublicKey union for the ed25519 key
+const PUBLIC_KEY_TYPE_ED25519: u32 = 0;
+
+/// Length of the StrKey encoded account id
+const ACCOUNT_ID_LEN: usize = 56;
 
 pub struct CPAsset {
     pub(crate) code: [u8; 12],
     pub(crate) issuer: SdkString,
 }
 
+impl CPAsset {
+    /// Return the length of the asset code without zero padding
+    pub fn code_len(&self) -> usize {
+        self.code.iter().position(|c| *c == 0).unwrap_or(self.code.len())
+    }
+}
+
 impl IntoVal<Env, Bytes> for CPAsset {
     fn into_val(&self, e: &Env) -> Bytes {
         self.to_xdr(e).unwrap()
@@ -15,59 +33,165 @@ impl IntoVal<Env, Bytes> for CPAsset {
 }
 
 impl CPWriteXdr for CPAsset {
+    /// Serialize the asset as the stellar Asset union:
+    /// AssetType discriminant, AssetCode4 or AssetCode12 zero padded code
+    /// and the issuer AccountID as the PublicKey union
     fn to_xdr(&self, env: &Env) -> Result<Bytes, Error> {
-        let mut buffer = Bytes::new(&env);
-        let mut len:usize = 0;
-        for c in self.code {
-            if c == 0 {
-                break
-            }
-            len += 1;
+        let len = self.code_len();
+        if len < 1 {
+            return Err(Error::InvalidAssetCode);
         }
-        if len < 5 {
-            let mut asset_code4:[u8; 4] = [0u8; 4];
-            for i in 0.. 4 {
-                asset_code4[i] = self.code[i]
-            }
-            buffer.append(&Bytes::from_array(&env, &[0, 0, 0, 1]));
-            buffer.append(&Bytes::from_slice(&env, &asset_code4));
+        let mut buffer = Bytes::new(env);
+        if len <= 4 {
+            buffer.append(&Bytes::from_array(env, &ASSET_TYPE_CREDIT_ALPHANUM4.to_be_bytes()));
+            buffer.append(&Bytes::from_slice(env, &self.code[..4]));
         } else {
-            buffer.append(&Bytes::from_array(&env, &[0, 0, 0, 2]));
-            buffer.append(&Bytes::from_slice(&env, &self.code[..12]));
+            buffer.append(&Bytes::from_array(env, &ASSET_TYPE_CREDIT_ALPHANUM12.to_be_bytes()));
+            buffer.append(&Bytes::from_slice(env, &self.code[..12]));
         }
 
-        let account_bytes: &mut [u8; 56] = &mut [0u8; 56];
-        self.issuer.copy_into_slice(account_bytes);
-        let account_str = String::from_utf8(account_bytes
-            .to_vec()).map_err(|_| "Invalid UTF-8 sequence").unwrap();
-
-        let (_, key_bytes) = decode(account_str.as_str());
-
-        let mut key_array: [u8; 32] = [0; 32];
-        if key_bytes.len() == 32 {
-            key_array.copy_from_slice(&key_bytes);
-            let public_key = PublicKey::PublicKeyTypeEd25519(Uint256(key_array));
-            let acc_type: i32 = public_key.discriminant().into();
-            buffer.append(&Bytes::from_slice(&env, acc_type.to_le_bytes().as_slice()));
-            buffer.append(&Bytes::from_slice(&env, key_array.as_slice()));
-        } else {
-            let len: i32 = key_bytes.len().try_into().unwrap();
-            buffer.append(&len.to_xdr(&env));
-            buffer.append(&Bytes::from_slice(&env, key_bytes.as_slice()));
+        if self.issuer.len() as usize != ACCOUNT_ID_LEN {
+            return Err(Error::InvalidAddress);
         }
+        let account_bytes: &mut [u8; ACCOUNT_ID_LEN] = &mut [0u8; ACCOUNT_ID_LEN];
+        self.issuer.copy_into_slice(account_bytes);
+        let account_str = core::str::from_utf8(account_bytes)
+            .map_err(|_| Error::InvalidAddress)?;
+
+        // only stellar accounts can issue assets
+        let key_bytes = match StrKey::decode(account_str)? {
+            StrKey::Account(key) => key,
+            _ => return Err(Error::InvalidAddress),
+        };
+        buffer.append(&Bytes::from_array(env, &PUBLIC_KEY_TYPE_ED25519.to_be_bytes()));
+        buffer.append(&Bytes::from_slice(env, &key_bytes));
         Ok(buffer)
     }
 }
 
+impl CPReadXdr for CPAsset {
+    /// Deserialize the stellar Asset union of credit alphanum4 or alphanum12 type
+    fn from_xdr(env: &Env, xdr: &Bytes) -> Result<Self, Error> {
+        let code_len = match read_u32(xdr, 0)? {
+            ASSET_TYPE_CREDIT_ALPHANUM4 => 4,
+            ASSET_TYPE_CREDIT_ALPHANUM12 => 12,
+            _ => return Err(Error::InvalidXdr),
+        };
+        // discriminant, asset code, public key discriminant and ed25519 key
+        if xdr.len() as usize != 4 + code_len + 4 + 32 {
+            return Err(Error::InvalidXdr);
+        }
+        let mut code = [0u8; 12];
+        xdr.slice(4..(4 + code_len) as u32).copy_into_slice(&mut code[..code_len]);
+
+        let asset = CPAsset { code, issuer: SdkString::from_str(env, "") };
+        let len = asset.code_len();
+        let is_valid_code = match code_len {
+            4 => len >= 1,
+            _ => len >= 5,
+        };
+        if !is_valid_code
+            || !code[..len].iter().all(|c| c.is_ascii_alphanumeric())
+            || !code[len..].iter().all(|c| *c == 0) {
+            return Err(Error::InvalidAssetCode);
+        }
+
+        let key_offset = 4 + code_len as u32;
+        if read_u32(xdr, key_offset)? != PUBLIC_KEY_TYPE_ED25519 {
+            return Err(Error::InvalidXdr);
+        }
+        let mut key = [0u8; 32];
+        xdr.slice(key_offset + 4..key_offset + 36).copy_into_slice(&mut key);
+        let issuer = StrKey::Account(key).encode();
+
+        Ok(CPAsset { code, issuer: SdkString::from_str(env, issuer.as_str()) })
+    }
+}
+
 pub trait CPWriteXdr {
     fn to_xdr(&self, env: &Env) -> Result<Bytes, Error>;
 }
 
-pub fn decode(s: &str) -> (u8, alloc::vec::Vec<u8>) {
-    let data = base32::decode(base32::Alphabet::Rfc4648 { padding: false }, s)
-        .unwrap();
-    let ver = data[0];
-    let (data_without_crc, _) = data.split_at(data.len() - 2);
-    let payload = &data_without_crc[1..];
-    (ver, payload.to_vec())
-}
\ No newline at end of file
+pub trait CPReadXdr: Sized {
+    fn from_xdr(env: &Env, xdr: &Bytes) -> Result<Self, Error>;
+}
+
+fn read_u32(xdr: &Bytes, offset: u32) -> Result<u32, Error> {
+    if xdr.len() < offset + 4 {
+        return Err(Error::InvalidXdr);
+    }
+    let mut value = [0u8; 4];
+    xdr.slice(offset..offset + 4).copy_into_slice(&mut value);
+    Ok(u32::from_be_bytes(value))
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::{CPAsset, CPReadXdr, CPWriteXdr};
+    use crate::error::Error;
+    use core::str::FromStr;
+    use soroban_sdk::xdr::{
+        AccountId, AlphaNum12, AlphaNum4, Asset, AssetCode12, AssetCode4, Limits, WriteXdr,
+    };
+    use soroban_sdk::{Bytes, Env, String};
+
+    const ISSUER: &str = "GCZMWP4RXU2QQV4ASC3PF6K4URGMBES4UR5GS2QYDUACL5SDSQWTDX52";
+
+    fn padded(code: &str) -> [u8; 12] {
+        let mut padded = [0u8; 12];
+        padded[..code.len()].copy_from_slice(code.as_bytes());
+        padded
+    }
+
+    /// XDR of the asset encoded by the stellar XDR library
+    fn stellar_xdr(env: &Env, asset: Asset) -> Bytes {
+        Bytes::from_slice(env, &asset.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn alphanum4(code: &str) -> Asset {
+        Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(padded(code)[..4].try_into().unwrap()),
+            issuer: AccountId::from_str(ISSUER).unwrap(),
+        })
+    }
+
+    fn alphanum12(code: &str) -> Asset {
+        Asset::CreditAlphanum12(AlphaNum12 {
+            asset_code: AssetCode12(padded(code)),
+            issuer: AccountId::from_str(ISSUER).unwrap(),
+        })
+    }
+
+    #[test]
+    fn round_trip_with_stellar_xdr() {
+        let env = Env::default();
+        for code in ["A", "USDC", "EURO1", "ABCDEFGHIJKL"] {
+            let asset = CPAsset { code: padded(code), issuer: String::from_str(&env, ISSUER) };
+            let expected = if code.len() <= 4 { alphanum4(code) } else { alphanum12(code) };
+
+            let xdr = asset.to_xdr(&env).unwrap();
+            assert_eq!(xdr, stellar_xdr(&env, expected));
+            let decoded = CPAsset::from_xdr(&env, &xdr).unwrap();
+            assert_eq!(decoded.code, asset.code);
+            assert_eq!(decoded.issuer, asset.issuer);
+        }
+    }
+
+    #[test]
+    fn from_xdr_rejects_invalid_asset() {
+        let env = Env::default();
+        // codes of up to 4 symbols must be encoded as alphanum4
+        let xdr = stellar_xdr(&env, alphanum12("USD"));
+        assert!(matches!(CPAsset::from_xdr(&env, &xdr), Err(Error::InvalidAssetCode)));
+
+        let xdr = stellar_xdr(&env, alphanum4("USD"));
+        assert!(matches!(
+            CPAsset::from_xdr(&env, &xdr.slice(..xdr.len() - 1)),
+            Err(Error::InvalidXdr)
+        ));
+        let xdr = stellar_xdr(&env, Asset::Native);
+        assert!(matches!(CPAsset::from_xdr(&env, &xdr), Err(Error::InvalidXdr)));
+    }
+}
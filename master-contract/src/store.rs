@@ -1,14 +1,25 @@
 use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+use crate::config::Config;
 use crate::error::Error;
+use crate::ttl::Ttl;
 use crate::store::StorageKey::Transfers;
 
+/// Config is the smart contract configuration in the instance storage. Value is a Config
+pub(crate) const CONFIG: Symbol = symbol_short!("Config");
+
+/// Schema is a version of the storage schema. Value is an u32
+pub(crate) const SCHEMA: Symbol = symbol_short!("Schema");
+
+// The configuration was kept in separate persistent entries before it was moved
+// into the instance storage, these keys are read only to migrate deployed smart contracts
+
 /// Admin is an address that authorized to sign the contract. Value is an Address
 pub(crate) const ADMIN: Symbol = symbol_short!("Admin");
 
 /// FeeAcc is an address that collects fee from smart contract operations. Value is an Address
 pub(crate) const FEE_ACCOUNT: Symbol = symbol_short!("FeeAcc");
 
-/// LastAsset is a name of the last-used asset for smart contract generation. Value is a Symbol
+/// LastAsset is a name of the last-used asset for smart contract generation. Value is a String
 pub(crate) const LAST_ASSET: Symbol = symbol_short!("LastAsset");
 
 /// PayAsset is a name of the last used asset for smart contract generation. Value is an OrderInfo
@@ -17,6 +28,15 @@ pub(crate) const PAY_ASSET: Symbol = symbol_short!("PayAsset");
 /// LastBurn is a counter of burns calls. Value is an u64
 pub(crate) const LAST_BURN: Symbol = symbol_short!("LastBurn");
 
+#[contracttype]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PayMode {
+    /// Pay asset is issued by the admin and minted for every payout
+    Mint = 1,
+    /// Pay asset is an existing asset transferred from the smart contract treasury
+    Treasury = 2,
+}
+
 #[contracttype]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct OrderInfo {
@@ -46,6 +66,60 @@ pub struct TransferInfo {
     pub(crate) date: Option<u64>,
 }
 
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RateInfo {
+    pub base: String,
+    pub quote: String,
+    /// price of one 'base' unit in 'quote' scaled by 10^7
+    pub rate: i128,
+    /// timestamp the rate is set at
+    pub date: u64,
+}
+
+/// PayoutInfo is an approved transfer paid out in the settlement pay asset of the order
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PayoutInfo {
+    pub transfer: String,
+    pub payer: String,
+    pub beneficiary: String,
+    /// amount of the order asset including the fee
+    pub amount: i128,
+    pub fee: i128,
+    pub date: Option<u64>,
+    /// rate of the order currency in the pay asset scaled by 10^7
+    pub rate: i128,
+    /// timestamp of the rate, none if the order is paid out 1:1
+    pub rate_date: Option<u64>,
+    /// amount paid out to the beneficiary in the pay asset
+    pub payout_amount: i128,
+    /// fee paid to the commission account in the pay asset
+    pub payout_fee: i128,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MintRequest {
+    pub order: String,
+    pub payment: String,
+    pub payer: String,
+    pub amount: i128,
+    pub fee: i128,
+    pub settlement: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransferRequest {
+    pub order: String,
+    pub transfer: String,
+    pub payer: String,
+    pub beneficiary: String,
+    pub amount: i128,
+    pub fee: i128,
+}
+
 #[contracttype]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct AssetInfo {
@@ -53,6 +127,7 @@ pub struct AssetInfo {
 }
 
 #[contracttype]
+#[derive(Clone)]
 pub enum StorageKey {
     /// Order is an order id that was used to issue an asset. Value is OrderInfo.
     Order(String),
@@ -64,11 +139,14 @@ pub enum StorageKey {
     /// Transfer is a list of transfer that was made by this smart contract for the asset.
     ///  Value is TransferInfo
     Transfers(String, Address),
-    /// Payouts is a list of payout that was made by this smart contract for the asset.
-    /// Value is TransferInfo
+    /// Payouts is a list of payout that was made by this smart contract for the asset
+    /// before the rates were recorded, all of them are paid out 1:1. Value is TransferInfo
     Payouts(String, Address),
+    /// PayoutRecords is a list of payout that was made by this smart contract for the asset
+    /// with the rate applied. Value is PayoutInfo
+    PayoutRecords(String, Address),
     /// Withdraw is a withdrawal payout request made by this smart contract for the pay asset.
-    /// Value is TransferInfo
+    /// Value is a list of one TransferInfo
     Withdraw(String),
     /// Burn is the executed burn payout approved by this smart contract for the pay asset.
     /// Value is TransferInfo
@@ -76,32 +154,102 @@ pub enum StorageKey {
     /// Payer is an id of user that do payment and receive confirmation as issued assets.
     /// Value is Address
     Payer(String),
+    /// Issuer is an address allowed to be used as an issuer of order assets.
+    /// Value is bool
+    Issuer(Address),
+    /// PayMode is a way to pay out the pay asset added by its code and issuer. Value is PayMode
+    PayMode(String, Address),
+    /// Settlement is the contract of the pay asset chosen for the order payouts.
+    /// Value is Address
+    Settlement(String),
+    /// WithdrawAsset is the contract of the pay asset burned for the withdrawal payout.
+    /// Value is Address
+    WithdrawAsset(String),
+    /// Rate is an exchange rate of the base currency in the quote currency set by the admin.
+    /// Value is RateInfo
+    Rate(String, String),
+    /// Denomination is a currency the order asset is denominated in. Value is String
+    Denomination(String),
+    /// OrderState is a lifecycle state and totals of the order. Value is Order
+    OrderState(String),
+    /// Refunds is a list of refunds made by this smart contract for the cancelled order asset.
+    /// Value is TransferInfo
+    Refunds(String, Address),
+    /// RefundCursor is an index of the next payment of the closed order to refund. Value is u32
+    RefundCursor(String),
 }
 
 
 pub fn get_order_info(env: &Env, order: &String) -> Result<OrderInfo, Error> {
-    Ok(env
-        .storage()
+    let storage_key = StorageKey::Order(order.clone());
+    Ttl::extend(env, &storage_key);
+    env.storage()
         .persistent()
-        .get(&StorageKey::Order(order.clone()))
-        .unwrap())
+        .get(&storage_key)
+        .ok_or(Error::OrderNotFound)
 }
 
 pub fn get_asset_info(env: &Env, code: &String, issuer: &Address) -> Result<AssetInfo, Error> {
-    Ok(env
-        .storage()
+    let storage_key = StorageKey::Asset(code.clone(), issuer.clone());
+    Ttl::extend(env, &storage_key);
+    env.storage()
         .persistent()
-        .get(&StorageKey::Asset(code.clone(), issuer.clone()))
-        .unwrap())
+        .get(&storage_key)
+        .ok_or(Error::AssetNotFound)
 }
 
 
 pub fn get_pay_asset_info(env: &Env) -> Result<OrderInfo, Error> {
-    Ok(env
+    Ok(Config::load(env)?.pay_asset)
+}
+
+/// Return the pay asset by its contract, the default pay asset or one of the added pay assets
+pub fn get_pay_asset(env: &Env, contract: &Address) -> Result<OrderInfo, Error> {
+    let config = Config::load(env)?;
+    if config.pay_asset.contract == *contract {
+        return Ok(config.pay_asset);
+    }
+    config
+        .pay_assets
+        .iter()
+        .find(|pay_asset| pay_asset.contract == *contract)
+        .ok_or(Error::UnknownPayAsset)
+}
+
+/// Return the pay asset referenced by a stored record,
+/// PayAssetMissing is returned if the pay asset was removed after the record was stored
+pub fn get_stored_pay_asset(env: &Env, contract: &Address) -> Result<OrderInfo, Error> {
+    get_pay_asset(env, contract).map_err(|error| match error {
+        Error::UnknownPayAsset => Error::PayAssetMissing,
+        error => error,
+    })
+}
+
+/// Return the pay asset chosen for the order payouts or the default pay asset
+pub fn get_settlement_asset_info(env: &Env, order: &String) -> Result<OrderInfo, Error> {
+    Ttl::extend(env, &StorageKey::Settlement(order.clone()));
+    match env
         .storage()
         .persistent()
-        .get(&PAY_ASSET)
-        .unwrap())
+        .get::<_, Address>(&StorageKey::Settlement(order.clone()))
+    {
+        Some(contract) => get_stored_pay_asset(env, &contract),
+        None => get_pay_asset_info(env),
+    }
+}
+
+/// Return the way to pay out the pay asset, the default pay asset uses the mode of the config
+pub fn get_pay_mode(env: &Env, pay_asset: &OrderInfo) -> PayMode {
+    let storage_key = StorageKey::PayMode(pay_asset.code.clone(), pay_asset.issuer.clone());
+    Ttl::extend(env, &storage_key);
+    if let Some(pay_mode) = env.storage().persistent().get(&storage_key) {
+        return pay_mode;
+    }
+    Config::load(env)
+        .ok()
+        .filter(|config| config.pay_asset == *pay_asset)
+        .map(|config| config.pay_mode)
+        .unwrap_or(PayMode::Mint)
 }
 
 pub fn get_stored_transfers(
@@ -110,8 +258,11 @@ pub fn get_stored_transfers(
     issuer: &Address,
 ) -> Result<Vec<TransferInfo>, Error> {
     let storage_key = Transfers(code.clone(), issuer.clone());
-    if !env.storage().persistent().has(&storage_key) {
-        return Err(Error::IncorrectTransfer);
-    }
-    Ok(env.storage().persistent().get(&storage_key).unwrap())
+    let transfers = env
+        .storage()
+        .persistent()
+        .get(&storage_key)
+        .ok_or(Error::IncorrectTransfer)?;
+    Ttl::extend(env, &storage_key);
+    Ok(transfers)
 }
\ No newline at end of file
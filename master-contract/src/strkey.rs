@@ -0,0 +1,199 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use soroban_sdk::Address;
+use crate::error::Error;
+
+/// StrKey version byte of the account id, encoded as 'G' as the first symbol
+const VERSION_BYTE_ACCOUNT_ID: u8 = 6 << 3;
+
+/// StrKey version byte of the contract id, encoded as 'C' as the first symbol
+const VERSION_BYTE_CONTRACT: u8 = 2 << 3;
+
+/// StrKey version byte of the muxed account, encoded as 'M' as the first symbol
+const VERSION_BYTE_MUXED_ACCOUNT: u8 = 12 << 3;
+
+/// Length of the StrKey encoded account id or contract id
+const KEY_LEN: usize = 56;
+
+/// Length of the StrKey encoded muxed account
+const MUXED_KEY_LEN: usize = 69;
+
+/// StrKey is a decoded stellar address
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StrKey {
+    /// Account is an ed25519 public key of the stellar account, G... address
+    Account([u8; 32]),
+    /// Contract is a hash of the smart contract id, C... address
+    Contract([u8; 32]),
+    /// Muxed is an ed25519 public key with the multiplexing id, M... address
+    Muxed([u8; 32], u64),
+}
+
+impl StrKey {
+    /// Decode StrKey string, the version byte, payload length
+    /// and CRC16-XModem checksum are verified
+    pub fn decode(s: &str) -> Result<StrKey, Error> {
+        if s.len() != KEY_LEN && s.len() != MUXED_KEY_LEN {
+            return Err(Error::InvalidAddress);
+        }
+        let data = base32::decode(base32::Alphabet::Rfc4648 { padding: false }, s)
+            .ok_or(Error::InvalidAddress)?;
+        if data.len() < 3 {
+            return Err(Error::InvalidAddress);
+        }
+        let (data_without_crc, crc) = data.split_at(data.len() - 2);
+        if crc16_xmodem(data_without_crc) != u16::from_le_bytes([crc[0], crc[1]]) {
+            return Err(Error::InvalidAddress);
+        }
+        let payload = &data_without_crc[1..];
+        let mut key = [0u8; 32];
+        match (data_without_crc[0], payload.len()) {
+            (VERSION_BYTE_ACCOUNT_ID, 32) => {
+                key.copy_from_slice(payload);
+                Ok(StrKey::Account(key))
+            }
+            (VERSION_BYTE_CONTRACT, 32) => {
+                key.copy_from_slice(payload);
+                Ok(StrKey::Contract(key))
+            }
+            (VERSION_BYTE_MUXED_ACCOUNT, 40) => {
+                key.copy_from_slice(&payload[..32]);
+                let mut id = [0u8; 8];
+                id.copy_from_slice(&payload[32..]);
+                Ok(StrKey::Muxed(key, u64::from_be_bytes(id)))
+            }
+            _ => Err(Error::InvalidAddress),
+        }
+    }
+
+    /// Encode StrKey into the string with the version byte and CRC16-XModem checksum
+    pub fn encode(&self) -> String {
+        let mut data = Vec::with_capacity(43);
+        match self {
+            StrKey::Account(key) => {
+                data.push(VERSION_BYTE_ACCOUNT_ID);
+                data.extend_from_slice(key);
+            }
+            StrKey::Contract(key) => {
+                data.push(VERSION_BYTE_CONTRACT);
+                data.extend_from_slice(key);
+            }
+            StrKey::Muxed(key, id) => {
+                data.push(VERSION_BYTE_MUXED_ACCOUNT);
+                data.extend_from_slice(key);
+                data.extend_from_slice(&id.to_be_bytes());
+            }
+        }
+        let crc = crc16_xmodem(&data);
+        data.extend_from_slice(&crc.to_le_bytes());
+        base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &data)
+    }
+
+    /// Decode the address of the account or smart contract
+    pub fn from_address(address: &Address) -> Result<StrKey, Error> {
+        let address_str = address.to_string();
+        let len = address_str.len() as usize;
+        if len != KEY_LEN {
+            return Err(Error::InvalidAddress);
+        }
+        let buffer: &mut [u8; KEY_LEN] = &mut [0u8; KEY_LEN];
+        address_str.copy_into_slice(buffer);
+        let s = core::str::from_utf8(buffer).map_err(|_| Error::InvalidAddress)?;
+        StrKey::decode(s)
+    }
+}
+
+/// Return the ed25519 public key of the address if the address is a stellar account,
+/// smart contracts and muxed accounts can't issue stellar assets
+pub fn account_id(address: &Address) -> Result<[u8; 32], Error> {
+    match StrKey::from_address(address)? {
+        StrKey::Account(key) => Ok(key),
+        _ => Err(Error::InvalidAddress),
+    }
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::{account_id, StrKey};
+    use crate::error::Error;
+    use core::str::FromStr;
+    use soroban_sdk::xdr::{
+        AccountId, Hash, MuxedAccount, MuxedAccountMed25519, PublicKey, ScAddress, Uint256,
+    };
+    use soroban_sdk::{Address, Env};
+    use std::string::ToString;
+
+    const ACCOUNT: &str = "GCZMWP4RXU2QQV4ASC3PF6K4URGMBES4UR5GS2QYDUACL5SDSQWTDX52";
+
+    /// Ed25519 key of the account decoded by the stellar XDR library
+    fn account_key() -> [u8; 32] {
+        let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))) =
+            AccountId::from_str(ACCOUNT).unwrap();
+        key
+    }
+
+    #[test]
+    fn account_round_trip() {
+        let key = StrKey::decode(ACCOUNT).unwrap();
+        assert_eq!(key, StrKey::Account(account_key()));
+        assert_eq!(key.encode(), ACCOUNT);
+
+        let env = Env::default();
+        let address = Address::from_str(&env, ACCOUNT);
+        assert_eq!(account_id(&address).unwrap(), account_key());
+    }
+
+    #[test]
+    fn contract_round_trip() {
+        let contract = ScAddress::Contract(Hash([7; 32])).to_string();
+        let key = StrKey::decode(&contract).unwrap();
+        assert_eq!(key, StrKey::Contract([7; 32]));
+        assert_eq!(key.encode(), contract);
+
+        // smart contracts can't issue stellar assets
+        let env = Env::default();
+        let address = Address::from_str(&env, &contract);
+        assert_eq!(StrKey::from_address(&address).unwrap(), key);
+        assert!(matches!(account_id(&address), Err(Error::InvalidAddress)));
+    }
+
+    #[test]
+    fn muxed_round_trip() {
+        let muxed = MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+            id: 42,
+            ed25519: Uint256(account_key()),
+        })
+        .to_string();
+        let key = StrKey::decode(&muxed).unwrap();
+        assert_eq!(key, StrKey::Muxed(account_key(), 42));
+        assert_eq!(key.encode(), muxed);
+    }
+
+    #[test]
+    fn decode_verifies_checksum_and_length() {
+        let mut corrupted = ACCOUNT.to_string();
+        corrupted.replace_range(10..11, "A");
+        assert!(matches!(StrKey::decode(&corrupted), Err(Error::InvalidAddress)));
+        assert!(matches!(StrKey::decode(&ACCOUNT[1..]), Err(Error::InvalidAddress)));
+        assert!(matches!(
+            StrKey::decode("GCZMWP4RXU2QQV4ASC3PF6K4URGMBES4UR5GS2QYDUACL5SDSQWTDX5!"),
+            Err(Error::InvalidAddress)
+        ));
+    }
+}
@@ -1,24 +1,30 @@
 use crate::commission::Commission;
+use crate::config::Config;
 use crate::error::Error;
-use crate::minting::MintClient;
+use contract_types::mint::MintClient;
+use crate::order::Order;
 use crate::payer::Payer;
-use crate::store::StorageKey::{Payouts, Transfers};
+use crate::payout::Payout;
+use crate::rate::{Rate, RATE_SCALE};
+use crate::store::StorageKey::{PayoutRecords, Payouts, Transfers};
 use crate::store::{
-    get_asset_info, get_order_info, get_pay_asset_info, get_stored_transfers, OrderInfo,
-    StorageKey, TransferInfo, ADMIN,
+    get_asset_info, get_order_info, get_settlement_asset_info, get_stored_transfers, OrderInfo,
+    PayoutInfo, StorageKey, TransferInfo,
 };
+use crate::ttl::Ttl;
 use soroban_sdk::{vec, Address, Env, String, Vec};
 
 pub struct Transfer;
 
 impl Transfer {
     fn get_admin(env: &Env) -> Result<Address, Error> {
-        Ok(env.storage().persistent().get(&ADMIN).unwrap())
+        Config::admin(env)
     }
 
     fn validate_admin(env: &Env) -> Result<(), Error> {
         let admin = Self::get_admin(env)?;
         admin.require_auth();
+        Ttl::extend_config(env);
         Ok(())
     }
 
@@ -29,15 +35,14 @@ impl Transfer {
         transfer_info: TransferInfo,
     ) -> Result<(), Error> {
         let storage_key = Transfers(code.clone(), issuer.clone());
-        let transfers = if env.storage().persistent().has(&storage_key) {
-            let mut existing_transfers: Vec<TransferInfo> =
-                env.storage().persistent().get(&storage_key).unwrap();
-            existing_transfers.push_back(transfer_info);
-            existing_transfers
-        } else {
-            vec![env, transfer_info]
-        };
+        let mut transfers: Vec<TransferInfo> = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .unwrap_or(vec![env]);
+        transfers.push_back(transfer_info);
         env.storage().persistent().set(&storage_key, &transfers);
+        Ttl::extend(env, &storage_key);
         Ok(())
     }
 
@@ -66,7 +71,20 @@ impl Transfer {
         fee: i128,
     ) -> Result<(), Error> {
         Self::validate_admin(&env)?;
+        Self::transfer_authorized(env, order, transfer, payer, beneficiary, amount, fee)
+    }
 
+    /// Transfer the order asset like 'transfer', the admin signature is already required
+    /// by the caller, an address can be authorized only once in the invocation
+    pub(crate) fn transfer_authorized(
+        env: Env,
+        order: String,
+        transfer: String,
+        payer: String,
+        beneficiary: String,
+        amount: i128,
+        fee: i128,
+    ) -> Result<(), Error> {
         // Verify the amount is positive after commission deduction
         if amount - fee < 0 {
             return Err(Error::NegativeAmount);
@@ -74,6 +92,9 @@ impl Transfer {
 
         let order_info = get_order_info(&env, &order)?;
         let asset_info = get_asset_info(&env, &order_info.code, &order_info.issuer)?;
+        let mut state = Order::load(&env, &order)?;
+        state.on_transfer(amount, env.ledger().timestamp())?;
+        state.save(&env);
 
         let transfer_info = TransferInfo {
             transfer,
@@ -86,14 +107,13 @@ impl Transfer {
         Self::update_transfer_records(&env, &order_info.code, &order_info.issuer, transfer_info)?;
 
         let client = MintClient::new(&env, &order_info.contract);
-        let from = Payer::payer(env.clone(), payer);
+        let from = Payer::payer(env.clone(), payer)?;
 
         client.clawback(&from, &amount);
 
-        env.storage().persistent().set(
-            &StorageKey::Asset(order_info.code, order_info.issuer),
-            &asset_info,
-        );
+        let asset_key = StorageKey::Asset(order_info.code, order_info.issuer);
+        env.storage().persistent().set(&asset_key, &asset_info);
+        Ttl::extend(&env, &asset_key);
 
         Ok(())
     }
@@ -102,24 +122,71 @@ impl Transfer {
         env: &Env,
         code: &String,
         issuer: &Address,
-        payout_info: TransferInfo,
+        payout_info: PayoutInfo,
     ) -> Result<(), Error> {
-        let storage_key = Payouts(code.clone(), issuer.clone());
-        let payouts = if env.storage().persistent().has(&storage_key) {
-            let mut existing_payouts: Vec<TransferInfo> =
-                env.storage().persistent().get(&storage_key).unwrap();
-            existing_payouts.push_back(payout_info);
-            existing_payouts
-        } else {
-            vec![env, payout_info]
-        };
+        let storage_key = PayoutRecords(code.clone(), issuer.clone());
+        Ttl::extend(env, &storage_key);
+        let mut payouts: Vec<PayoutInfo> = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .unwrap_or(vec![env]);
+        payouts.push_back(payout_info);
         env.storage().persistent().set(&storage_key, &payouts);
+        Ttl::extend(env, &storage_key);
         Ok(())
     }
 
+    /// Return the payouts of the order with the rates applied,
+    /// payouts recorded before the rates are returned first with the rate 1:1
+    pub fn payouts(env: Env, order: String) -> Result<Vec<PayoutInfo>, Error> {
+        let order_info = get_order_info(&env, &order)?;
+        let (code, issuer) = (order_info.code, order_info.issuer);
+        Ttl::extend(&env, &Payouts(code.clone(), issuer.clone()));
+        Ttl::extend(&env, &PayoutRecords(code.clone(), issuer.clone()));
+        let legacy: Vec<TransferInfo> = env
+            .storage()
+            .persistent()
+            .get(&Payouts(code.clone(), issuer.clone()))
+            .unwrap_or(vec![&env]);
+        let mut payouts = vec![&env];
+        for payout in legacy.iter() {
+            payouts.push_back(PayoutInfo {
+                payout_amount: payout.amount - payout.fee,
+                payout_fee: payout.fee,
+                transfer: payout.transfer,
+                payer: payout.payer,
+                beneficiary: payout.beneficiary,
+                amount: payout.amount,
+                fee: payout.fee,
+                date: payout.date,
+                rate: RATE_SCALE,
+                rate_date: None,
+            });
+        }
+        let recorded: Vec<PayoutInfo> = env
+            .storage()
+            .persistent()
+            .get(&PayoutRecords(code, issuer))
+            .unwrap_or(vec![&env]);
+        payouts.append(&recorded);
+        Ok(payouts)
+    }
+
     /// Calls the 'approve_transfer' function of the 'contract' to unfreeze assets.
     pub fn approve_transfer(env: Env, order: String, transfer: String) -> Result<(), Error> {
+        Self::approve(env, order, transfer).map(|_| ())
+    }
+
+    /// Approve the transfer and return the amount paid out in the settlement pay asset.
+    pub fn approve(env: Env, order: String, transfer: String) -> Result<i128, Error> {
         Self::validate_admin(&env)?;
+        Self::approve_authorized(env, order, transfer)
+    }
+
+    /// Approve the transfer like 'approve', the admin signature is already required
+    /// by the caller, an address can be authorized only once in the invocation
+    pub(crate) fn approve_authorized(env: Env, order: String, transfer: String) -> Result<i128, Error> {
         let order_info = get_order_info(&env, &order)?;
 
         // Find and remove the transfer record
@@ -133,28 +200,40 @@ impl Transfer {
             &recorded_transfers,
         );
 
-        // Create and store payout record
-        let payout_info = TransferInfo {
+        let mut state = Order::load(&env, &order)?;
+        state.on_transfer_approved(approved_transfer.amount, approved_transfer.fee);
+        state.save(&env);
+
+        // Perform asset swap
+        let beneficiary_address = Payer::payer(env.clone(), approved_transfer.beneficiary.clone())?;
+
+        let pay_asset: OrderInfo = get_settlement_asset_info(&env, &order)?;
+        let rate = Rate::order_rate(&env, &order, &pay_asset.code)?;
+        let payout_amount = Rate::convert(&rate, approved_transfer.amount - approved_transfer.fee)?;
+        let payout_fee = Rate::convert(&rate, approved_transfer.fee)?;
+
+        // Create and store payout record with the applied rate
+        let payout_info = PayoutInfo {
             transfer,
             payer: approved_transfer.payer,
-            beneficiary: approved_transfer.beneficiary.clone(),
+            beneficiary: approved_transfer.beneficiary,
             amount: approved_transfer.amount,
             fee: approved_transfer.fee,
             date: Option::from(env.ledger().timestamp()),
+            rate: rate.as_ref().map(|rate| rate.rate).unwrap_or(RATE_SCALE),
+            rate_date: rate.map(|rate| rate.date),
+            payout_amount,
+            payout_fee,
         };
         Self::update_payout_records(&env, &order_info.code, &order_info.issuer, payout_info)?;
-
-        // Perform asset swap
-        let beneficiary_address = Payer::payer(env.clone(), approved_transfer.beneficiary);
-
-        let pay_asset: OrderInfo = get_pay_asset_info(&env)?;
-        let client_payout = MintClient::new(&env, &pay_asset.contract);
-        client_payout.mint(
+        Payout::pay(
+            &env,
+            &pay_asset,
             &beneficiary_address,
-            &(approved_transfer.amount - approved_transfer.fee),
+            &payout_amount,
         );
-        let _ = Commission::pay_commission(env.clone(), &approved_transfer.fee);
-        Ok(())
+        let _ = Commission::pay_commission(env.clone(), &pay_asset, &payout_fee);
+        Ok(payout_amount)
     }
 
     /// Calls the 'reject_transfer' function of the 'contract' to recall assets to a payer account.
@@ -173,9 +252,13 @@ impl Transfer {
             &recorded_transfers,
         );
 
+        let mut state = Order::load(&env, &order)?;
+        state.on_transfer_rejected(rejected_transfer.amount);
+        state.save(&env);
+
         // Perform asset reallocation
         let client = MintClient::new(&env, &order_info.contract);
-        let payer = Payer::payer(env.clone(), rejected_transfer.payer);
+        let payer = Payer::payer(env.clone(), rejected_transfer.payer)?;
 
         client.mint(&payer, &rejected_transfer.amount);
 
@@ -0,0 +1,81 @@
+use soroban_sdk::{Env, IntoVal, String, Val, Vec};
+use crate::config::Config;
+use crate::store::StorageKey::{
+    Asset, Denomination, Order, OrderState, Payments, PayoutRecords, Payouts, RefundCursor, Refunds, Settlement,
+    Transfers,
+};
+use crate::store::{get_order_info, StorageKey};
+use crate::error::Error;
+use marketplace_asset::ttl::{
+    INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT,
+    PERSISTENT_LIFETIME_THRESHOLD,
+};
+
+// Storage placement:
+// - instance storage keeps the smart contract instance, its code and the configuration alive
+// - persistent storage keeps orders, payers, issuers, pay assets and rates,
+//   entries are extended when they are used and can be extended explicitly
+// - temporary storage is not used, every entry must survive until the order is closed
+// - the extension periods are shared with the goods and demand contracts
+
+pub struct Ttl;
+
+impl Ttl {
+    /// Extend the smart contract instance and its code
+    pub fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Extend the persistent entry if it exists
+    pub fn extend<K>(env: &Env, key: &K)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(
+                key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Extend the smart contract instance, the configuration lives in the instance storage
+    pub fn extend_config(env: &Env) {
+        Self::extend_instance(env);
+    }
+
+    /// Extend the configuration and the given persistent entries.
+    pub fn extend_all(env: Env, keys: Vec<StorageKey>) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+
+        Self::extend_config(&env);
+        for key in keys.iter() {
+            Self::extend(&env, &key);
+        }
+        Ok(())
+    }
+
+    /// Extend all entries of the order.
+    pub fn bump_order(env: Env, order: String) -> Result<(), Error> {
+        Config::require_admin(&env)?;
+
+        Self::extend_config(&env);
+        let order_info = get_order_info(&env, &order)?;
+        let (code, issuer) = (order_info.code, order_info.issuer);
+        Self::extend(&env, &Order(order.clone()));
+        Self::extend(&env, &OrderState(order.clone()));
+        Self::extend(&env, &RefundCursor(order.clone()));
+        Self::extend(&env, &Settlement(order.clone()));
+        Self::extend(&env, &Denomination(order));
+        Self::extend(&env, &Asset(code.clone(), issuer.clone()));
+        Self::extend(&env, &Payments(code.clone(), issuer.clone()));
+        Self::extend(&env, &Transfers(code.clone(), issuer.clone()));
+        Self::extend(&env, &Payouts(code.clone(), issuer.clone()));
+        Self::extend(&env, &PayoutRecords(code.clone(), issuer.clone()));
+        Self::extend(&env, &Refunds(code, issuer));
+        Ok(())
+    }
+}
\ No newline at end of file
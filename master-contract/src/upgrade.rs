@@ -1,21 +1,23 @@
-use soroban_sdk::{BytesN, Env, Address, String};
-use crate::store::{ADMIN};
+use crate::schema::Schema;
+use marketplace_asset::upgrade::Upgradeable;
+use marketplace_asset::Error;
+use soroban_sdk::{Address, Env, String};
 
 pub struct UpgradeableContract;
 
 impl UpgradeableContract {
 
     pub fn version_build(env: Env) -> String {
-        String::from_str(&env, "0.0.1")
+        String::from_str(&env, env!("CARGO_PKG_VERSION"))
     }
 
     pub fn version() -> i32 {
         3
     }
+}
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env.storage().persistent().get(&ADMIN).unwrap();
-        admin.require_auth();
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+impl Upgradeable for UpgradeableContract {
+    fn upgrade_admin(env: &Env) -> Result<Address, Error> {
+        Schema::admin(env, Schema::version(env)).map_err(|_| Error::NotInitialized)
     }
-}
\ No newline at end of file
+}